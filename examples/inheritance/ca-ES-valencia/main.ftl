@@ -0,0 +1 @@
+variant = Valencià
@@ -0,0 +1 @@
+region = Espanya
@@ -0,0 +1,3 @@
+language = Català
+region = Internacional
+variant = Central
@@ -0,0 +1 @@
+region = 台灣
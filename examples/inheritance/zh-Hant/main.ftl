@@ -0,0 +1 @@
+script = 繁體
@@ -0,0 +1,3 @@
+language = 中文
+region = 国际
+script = 简体
@@ -0,0 +1 @@
+bus = Machimbombo
@@ -0,0 +1 @@
+# kept blank to generate pt-MZ
@@ -0,0 +1,2 @@
+bus = Autocarro
+team = Equipa
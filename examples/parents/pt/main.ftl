@@ -0,0 +1,3 @@
+bus = Ônibus
+team = Time
+hello = Olá
@@ -1,16 +1,15 @@
 use actix_web::{http::header::ACCEPT_LANGUAGE, HttpRequest};
-use std::boxed::Box;
 
-use super::{machine::TranslateFn, FluentMachine, LanguageIdentifier};
+use super::{FluentMachine, LanguageIdentifier, Localizer};
 
 /// Implementation for actix-web
 #[cfg(feature = "actix-web4")]
 impl FluentMachine {
-    /// Returns translate closure, with resolved locales for request.
+    /// Returns [`Localizer`], with resolved locales for request.
     ///
     /// # Locale resolution
     /// If is set [`FluentMachineBuilder::set_cookie_name`](crate::builders::FluentMachineBuilder::set_cookie_name)
-    /// and is a match to [supported_locales](crate::FluentMachine::get_supported_locales) else
+    /// and is a match, or an alias of, to [supported_locales](crate::FluentMachine::get_supported_locales) else
     /// resolves using [`FluentMachine::negotiate_languages`] according to
     /// [`actix_web::http::header::ACCEPT_LANGUAGE`] headers
     ///
@@ -23,7 +22,7 @@ impl FluentMachine {
     ///
     /// async fn index(req: HttpRequest, i18n: web::Data<FluentMachine>) -> String {
     ///     let t = i18n.from_request_tanslate(&req);
-    ///     t(
+    ///     t.t(
     ///         "movie-list".try_into().unwrap(),
     ///         Some(&f_args![
     ///             "movies" => 5,
@@ -54,17 +53,14 @@ impl FluentMachine {
     /// ```
     #[inline]
     #[cfg_attr(docsrs, doc(cfg(feature = "actix-web4")))]
-    pub fn from_request_tanslate(&self, request: &HttpRequest) -> TranslateFn<'_> {
+    pub fn from_request_tanslate(&self, request: &HttpRequest) -> Localizer<'_> {
         if let Some(cookie_name) = &self.cookie_name {
-            if let Some(lang) = request
-                .cookie(&cookie_name)
-                .map(|f| String::from(f.value()))
-            {
-                match lang.parse::<LanguageIdentifier>() {
-                    Ok(lang) if self.available.contains(&lang) => {
-                        return Box::new(move |key, options| self.t(&[&lang], key, options))
+            if let Some(lang) = request.cookie(cookie_name).map(|f| String::from(f.value())) {
+                if let Ok(lang) = lang.parse::<LanguageIdentifier>() {
+                    let lang = self.resolve_alias(&lang);
+                    if let Some(lang) = self.available.iter().find(|l| *l == lang) {
+                        return Localizer::new(self, vec![lang]);
                     }
-                    _ => (),
                 }
             }
         }
@@ -119,7 +115,7 @@ region = United States
                 ))
                 .to_http_request(),
         );
-        assert_eq!(t_en("region".try_into().unwrap(), None), "United States");
+        assert_eq!(t_en.t("region".try_into().unwrap(), None), "United States");
         let t_en = i18n.from_request_tanslate(
             &TestRequest::get()
                 .insert_header((
@@ -128,13 +124,13 @@ region = United States
                 ))
                 .to_http_request(),
         );
-        assert_eq!(t_en("region".try_into().unwrap(), None), "United Kingdom");
+        assert_eq!(t_en.t("region".try_into().unwrap(), None), "United Kingdom");
         let t_en = i18n.from_request_tanslate(
             &TestRequest::get()
                 .insert_header((actix_web::http::header::ACCEPT_LANGUAGE, "de-DE;0.9,de;0.7"))
                 .to_http_request(),
         );
-        assert_eq!(t_en("region".try_into().unwrap(), None), "International");
+        assert_eq!(t_en.t("region".try_into().unwrap(), None), "International");
     }
 
     #[actix_web::test]
@@ -159,7 +155,7 @@ region = International
                 ))
                 .to_http_request(),
         );
-        assert_eq!(t("region".try_into().unwrap(), None), "International");
+        assert_eq!(t.t("region".try_into().unwrap(), None), "International");
     }
 
     #[actix_web::test]
@@ -192,7 +188,7 @@ region = Brazil
                 .cookie(Cookie::new("locale", "pt-BR"))
                 .to_http_request(),
         );
-        assert_eq!(t("region".try_into().unwrap(), None), "Brazil");
+        assert_eq!(t.t("region".try_into().unwrap(), None), "Brazil");
     }
 
     #[actix_web::test]
@@ -225,6 +221,38 @@ region = Brazil
                 .cookie(Cookie::new("locale", "jp"))
                 .to_http_request(),
         );
-        assert_eq!(t("region".try_into().unwrap(), None), "International");
+        assert_eq!(t.t("region".try_into().unwrap(), None), "International");
+    }
+
+    #[actix_web::test]
+    async fn actix_request_tanslate_alias() {
+        let i18n = FluentMachine::build()
+            .add_resource_override("en", "region = International")
+            .expect("failed to add en")
+            .add_resource_override("en-UK", "region = United Kingdom")
+            .expect("failed to add en-UK")
+            .add_resource_override("pt-PT", "region = Portugal")
+            .expect("failed to add pt-PT")
+            .add_alias("en-GB", "en-UK")
+            .expect("failed to add en-GB alias")
+            .add_alias("pt-AO", "pt-PT")
+            .expect("failed to add pt-AO alias")
+            .set_cookie_name("locale")
+            .finish()
+            .unwrap();
+
+        let t = i18n.from_request_tanslate(
+            &TestRequest::get()
+                .insert_header((actix_web::http::header::ACCEPT_LANGUAGE, "en-GB,en;0.5"))
+                .to_http_request(),
+        );
+        assert_eq!(t.t("region".try_into().unwrap(), None), "United Kingdom");
+        let t = i18n.from_request_tanslate(
+            &TestRequest::get()
+                .insert_header((actix_web::http::header::ACCEPT_LANGUAGE, "en-GB,en;0.5"))
+                .cookie(Cookie::new("locale", "pt-AO"))
+                .to_http_request(),
+        );
+        assert_eq!(t.t("region".try_into().unwrap(), None), "Portugal");
     }
 }
@@ -0,0 +1,133 @@
+/*!
+Default arguments merged under the caller arguments
+*/
+
+use std::{borrow::Cow, fmt, sync::Arc};
+
+use fluent_bundle::types::FluentNumber;
+
+use crate::{FluentArgs, FluentValue};
+
+/// Owned default value, [`FluentValue`] isn't `Sync`
+enum DefaultValue {
+    String(String),
+    Number(FluentNumber),
+    None,
+}
+
+/// Arguments set with [`FluentMachineBuilder::set_default_args`](crate::builders::FluentMachineBuilder::set_default_args)
+/// or [`Localizer::with_default_args`](crate::Localizer::with_default_args).
+///
+/// Cheap to clone.
+#[derive(Clone)]
+pub(crate) struct DefaultArgs(Arc<[(String, DefaultValue)]>);
+
+/// Arguments passed to the bundles, the caller arguments are borrowed without default
+/// arguments
+pub(crate) enum MergedArgs<'a> {
+    Borrowed(Option<&'a FluentArgs<'a>>),
+    Owned(FluentArgs<'a>),
+}
+
+impl<'a> MergedArgs<'a> {
+    #[inline]
+    pub(crate) fn get(&self) -> Option<&FluentArgs<'a>> {
+        match self {
+            Self::Borrowed(args) => *args,
+            Self::Owned(args) => Some(args),
+        }
+    }
+}
+
+impl DefaultArgs {
+    /// Copies `args`, error and custom values are skipped.
+    pub(crate) fn new(args: &FluentArgs) -> Self {
+        let mut values: Vec<(String, DefaultValue)> = vec![];
+        for (key, value) in args.iter() {
+            let value = match value {
+                FluentValue::String(s) => DefaultValue::String(s.to_string()),
+                FluentValue::Number(n) => DefaultValue::Number(n.clone()),
+                FluentValue::None => DefaultValue::None,
+                FluentValue::Custom(_) => {
+                    log::warn!("default argument `{key}` skipped, custom values can't be shared");
+                    continue;
+                }
+                FluentValue::Error => continue,
+            };
+            values.push((key.to_string(), value));
+        }
+        Self(values.into())
+    }
+
+    /// Returns new arguments with the default arguments and `args`, the values of `args` win.
+    ///
+    /// The default strings are borrowed.
+    pub(crate) fn merge<'a>(&'a self, args: Option<&'a FluentArgs<'a>>) -> MergedArgs<'a> {
+        let caller = args.map_or(0, |a| a.iter().count());
+        let mut merged = FluentArgs::with_capacity(self.0.len() + caller);
+        for (key, value) in self.0.iter() {
+            let value = match value {
+                DefaultValue::String(s) => FluentValue::String(Cow::Borrowed(s)),
+                DefaultValue::Number(n) => FluentValue::Number(n.clone()),
+                DefaultValue::None => FluentValue::None,
+            };
+            merged.set(key.as_str(), value);
+        }
+        for (key, value) in args.into_iter().flat_map(FluentArgs::iter) {
+            merged.set(key, value.clone());
+        }
+        MergedArgs::Owned(merged)
+    }
+}
+
+impl fmt::Debug for DefaultArgs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.0.iter().map(|(key, _)| key))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DefaultArgs, MergedArgs};
+    use crate::{f_args, FluentValue};
+
+    #[test]
+    fn merges_under_args() {
+        let defaults = DefaultArgs::new(&f_args![
+            "app" => "fi18n",
+            "year" => 2026,
+            "broken" => FluentValue::Error,
+        ]);
+        assert_eq!(format!("{defaults:?}"), r#"["app", "year"]"#);
+
+        let merged = defaults.merge(None);
+        assert!(matches!(merged, MergedArgs::Owned(_)));
+        let merged = merged.get().unwrap();
+        assert_eq!(merged.get("app"), Some(&"fi18n".into()));
+        assert_eq!(merged.get("year"), Some(&2026.into()));
+        assert_eq!(merged.get("broken"), None);
+
+        let args = f_args!["year" => 2027, "name" => "Ana"];
+        let merged = defaults.merge(Some(&args));
+        assert!(matches!(merged, MergedArgs::Owned(_)));
+        let merged = merged.get().unwrap();
+        assert_eq!(merged.iter().count(), 3);
+        assert_eq!(merged.get("app"), Some(&"fi18n".into()));
+        assert_eq!(merged.get("year"), Some(&2027.into()));
+        assert_eq!(merged.get("name"), Some(&"Ana".into()));
+    }
+
+    #[cfg(feature = "fn-relative-time")]
+    #[test]
+    fn skips_custom_values() {
+        use crate::functions::FluentRelativeTime;
+
+        let defaults = DefaultArgs::new(&f_args![
+            "app" => "fi18n",
+            "posted" => FluentRelativeTime::new(-60),
+        ]);
+        assert_eq!(format!("{defaults:?}"), r#"["app"]"#);
+    }
+}
@@ -1,14 +1,15 @@
 use crate::{
-    error::FluentResourceError, machine::MachineBundles, Error, FluentResource, LanguageIdentifier,
-    MachineBundle,
+    error::{Diagnostic, FluentResourceError},
+    machine::MachineResources,
+    Error, FluentResource, LanguageIdentifier,
 };
 use ahash::RandomState;
 use std::{collections::HashMap, sync::Arc};
 
 use super::FluentMachineBuilder;
 
-/// a DRYer builder by _"inheritance"_, overwrites terms and messages from the base language
-/// to region specific, when calling [`build_inheritance`](FluentMachineInheritanceBuilder::build_inheritance)
+/// a DRYer builder by _"inheritance"_, overwrites terms and messages from the global, base
+/// language, script, region to variant specific, when calling [`build_inheritance`](FluentMachineInheritanceBuilder::build_inheritance)
 /// returns [`FluentMachineBuilder`] to continue configuration.
 ///
 /// ## Example
@@ -121,7 +122,30 @@ use super::FluentMachineBuilder;
 pub struct FluentMachineInheritanceBuilder {
     pub(crate) sources: HashMap<Option<LanguageIdentifier>, Vec<Arc<FluentResource>>>,
     pub(crate) errors: Vec<FluentResourceError>,
+    /// entries dropped in [`InheritanceSyntaxErrorHandling::Recover`] mode
+    pub(crate) diagnostics: Vec<Diagnostic>,
     pub(crate) mode: InheritanceSyntaxErrorHandling,
+    /// declared parents, replacing the parent derived from the tag
+    pub(crate) parents: HashMap<LanguageIdentifier, LanguageIdentifier>,
+}
+
+/// Parents of `locale`, nearest first, removing the variants, the region and the script.
+///
+/// `ca-ES-valencia` inherits from `ca-ES` and `ca`, `zh-Hant-TW` from `zh-Hant` and `zh`.
+pub(crate) fn parent_locales(locale: &LanguageIdentifier) -> Vec<LanguageIdentifier> {
+    let mut parents = Vec::with_capacity(3);
+    let mut parent = locale.clone();
+    if parent.variants().len() > 0 {
+        parent.clear_variants();
+        parents.push(parent.clone());
+    }
+    if parent.region.take().is_some() {
+        parents.push(parent.clone());
+    }
+    if parent.script.take().is_some() {
+        parents.push(parent);
+    }
+    parents
 }
 
 /// Mode of processing [`FluentMachineInheritanceBuilder`] syntax errors
@@ -162,6 +186,32 @@ pub enum InheritanceSyntaxErrorHandling {
     /// assert_eq!(fi18n.t(&langs, "bad-message".try_into().unwrap(), None), "bad-message");
     /// ```
     AtBuild,
+    /// Keeps the valid entries and drops the broken ones, the locale uses the parent version
+    /// of a dropped message. Never returns syntax errors, dropped entries are returned by
+    /// [`FluentMachineBuilder::diagnostics`] after
+    /// [`build_inheritance`](FluentMachineInheritanceBuilder::build_inheritance), and by
+    /// [`FluentMachine::diagnostics`](crate::FluentMachine::diagnostics)
+    ///
+    /// # Example:
+    /// ```
+    /// use fi18n::{FluentMachine, LanguageIdentifier, builders::InheritanceSyntaxErrorHandling};
+    ///
+    /// let builder = FluentMachine::build_with_inheritance(InheritanceSyntaxErrorHandling::Recover)
+    ///     .add_source("en", "ok = I'm OK\nwelcome = Welcome", None).expect("Should add en")
+    ///     .add_source("en-US", "ok = Okay\nwelcome = Welcome { $name", Some("en-US/main.ftl"))
+    ///     .expect("Should recover")
+    ///     .build_inheritance().expect("Should not return errors");
+    /// for dropped in builder.diagnostics() {
+    ///     eprintln!("{dropped}");
+    /// }
+    /// let i18n = builder.finish().expect("Should finish");
+    ///
+    /// let us: LanguageIdentifier = "en-US".parse().unwrap();
+    /// assert_eq!(i18n.t(&[&us], "ok".try_into().unwrap(), None), "Okay");
+    /// assert_eq!(i18n.t(&[&us], "welcome".try_into().unwrap(), None), "Welcome");
+    /// assert_eq!(i18n.diagnostics()[0].id.as_deref(), Some("welcome"));
+    /// ```
+    Recover,
 }
 
 /// Syntax errors can be returned at two points, every time at [`add_source`] or when [`build_inheritance`]
@@ -175,14 +225,85 @@ impl FluentMachineInheritanceBuilder {
             mode,
             sources: HashMap::default(),
             errors: Vec::new(),
+            diagnostics: Vec::new(),
+            parents: HashMap::default(),
+        }
+    }
+
+    /// Declare the parent of `child`, replacing the parent derived from the tag, `pt-AO`
+    /// inherits from `pt-PT` instead of `pt`. The parent inherits as usual, from its declared
+    /// parent or from the tag.
+    ///
+    /// # Arguments:
+    /// * `child` a valid locale
+    /// * `parent` a valid locale, inherited by `child`
+    ///
+    /// # Errors
+    /// Returns [`Error::LanguageIdentifierError`] if a locale is invalid
+    ///
+    /// Returns [`Error::InheritanceCycle`] if `child` is an ancestor of `parent`
+    ///
+    /// # Example
+    /// ```
+    /// use fi18n::{FluentMachine, LanguageIdentifier, builders::InheritanceSyntaxErrorHandling};
+    ///
+    /// let i18n = FluentMachine::build_with_inheritance(InheritanceSyntaxErrorHandling::AtBuild)
+    ///     .add_source("pt", "bus = Ônibus\ntrain = Trem\nhello = Olá", None).expect("Should add pt")
+    ///     .add_source("pt-PT", "bus = Autocarro\ntrain = Comboio", None).expect("Should add pt-PT")
+    ///     .add_source("pt-AO", "bus = Machimbombo", None).expect("Should add pt-AO")
+    ///     .set_parent("pt-AO", "pt-PT").expect("Should set parent")
+    ///     .build_inheritance().expect("Should build")
+    ///     .set_fallback_locale("pt").expect("Should set pt")
+    ///     .finish().expect("Should finish");
+    ///
+    /// let ao: LanguageIdentifier = "pt-AO".parse().unwrap();
+    /// assert_eq!(i18n.t(&[&ao], "bus".try_into().unwrap(), None), "Machimbombo");
+    /// assert_eq!(i18n.t(&[&ao], "train".try_into().unwrap(), None), "Comboio");
+    /// assert_eq!(i18n.t(&[&ao], "hello".try_into().unwrap(), None), "Olá");
+    /// ```
+    pub fn set_parent(mut self, child: &str, parent: &str) -> Result<Self, Error> {
+        let child = child.parse::<LanguageIdentifier>()?;
+        let parent = parent.parse::<LanguageIdentifier>()?;
+        let ancestors = self.ancestors(&parent);
+        if parent == child || ancestors.contains(&child) {
+            let mut cycle = vec![child.clone(), parent.clone()];
+            if parent != child {
+                cycle.extend(ancestors.into_iter().take_while(|l| *l != child));
+                cycle.push(child);
+            }
+            return Err(Error::InheritanceCycle(cycle));
+        }
+        self.parents.insert(child, parent);
+        Ok(self)
+    }
+
+    /// Ancestors of `locale`, nearest first, following the declared parents and the tag.
+    fn ancestors(&self, locale: &LanguageIdentifier) -> Vec<LanguageIdentifier> {
+        let mut ancestors: Vec<LanguageIdentifier> = Vec::new();
+        let mut current = locale.clone();
+        loop {
+            let parent = match self.parents.get(&current) {
+                Some(parent) => parent.clone(),
+                None => match parent_locales(&current).into_iter().next() {
+                    Some(parent) => parent,
+                    None => break,
+                },
+            };
+            // cycles are rejected by `set_parent`, guard against a declared ancestor anyway
+            if parent == *locale || ancestors.contains(&parent) {
+                break;
+            }
+            ancestors.push(parent.clone());
+            current = parent;
         }
+        ancestors
     }
 
     /// Add resource to builder
     ///
     /// # Arguments:
     /// * `locale` a valid locale with optional 2 regional letter code or an empty
-    ///     `str` to be shared between all locales
+    ///   `str` to be shared between all locales
     /// * `ftl` a Fluent resource
     /// * `origin` optional, used to format error with the origin
     ///
@@ -205,16 +326,24 @@ impl FluentMachineInheritanceBuilder {
 
         let rs = match FluentResource::try_new(ftl.to_string()) {
             Ok(rs) => Arc::new(rs),
-            Err((rs, exs)) => {
-                let err = FluentResourceError::new(ftl, origin, exs);
-                match self.mode {
-                    InheritanceSyntaxErrorHandling::AtAddSource => return Err(err.into()),
-                    InheritanceSyntaxErrorHandling::AtBuild => {
-                        self.errors.push(err);
-                        Arc::new(rs)
-                    }
+            Err((rs, exs)) => match self.mode {
+                InheritanceSyntaxErrorHandling::AtAddSource => {
+                    return Err(FluentResourceError::new(ftl, origin, exs).into())
                 }
-            }
+                InheritanceSyntaxErrorHandling::AtBuild => {
+                    self.errors.push(FluentResourceError::new(ftl, origin, exs));
+                    Arc::new(rs)
+                }
+                InheritanceSyntaxErrorHandling::Recover => {
+                    self.diagnostics.extend(Diagnostic::from_errors(
+                        locale.as_ref(),
+                        ftl,
+                        origin,
+                        &exs,
+                    ));
+                    Arc::new(rs)
+                }
+            },
         };
 
         self.sources
@@ -232,66 +361,51 @@ impl FluentMachineInheritanceBuilder {
     /// According to [`InheritanceSyntaxErrorHandling`]
     ///
     /// Returns [`Error::FluentResourceError`] if [`FluentMachineInheritanceBuilder`] uses [`InheritanceSyntaxErrorHandling::AtBuild`]
+    #[allow(clippy::result_large_err)]
     pub fn build_inheritance(self) -> Result<FluentMachineBuilder, BuildInheritanceError> {
         let mut available: Vec<LanguageIdentifier> =
             self.sources.keys().flatten().cloned().collect();
 
-        let mut bundles: MachineBundles =
+        let mut resources: MachineResources =
             HashMap::with_capacity_and_hasher(available.len(), RandomState::new());
         available.sort();
 
         for lang in available.into_iter() {
-            let mut bundle: MachineBundle = MachineBundle::new_concurrent(vec![lang.clone()]);
+            let mut layers: Vec<Arc<FluentResource>> = vec![];
 
             if let Some(global) = self.sources.get(&None) {
-                for src in global {
-                    bundle.add_resource_overriding(src.to_owned());
-                }
+                layers.extend(global.iter().cloned());
             }
 
-            if lang.region.is_some() {
-                if let Some(base_lang) = self.sources.get(&Some(LanguageIdentifier::from_parts(
-                    lang.language,
-                    lang.script,
-                    None,
-                    &[],
-                ))) {
-                    for src in base_lang {
-                        bundle.add_resource_overriding(src.to_owned());
-                    }
+            // farthest parent first, each level overrides the previous
+            for parent in self.ancestors(&lang).into_iter().rev() {
+                if let Some(parent) = self.sources.get(&Some(parent)) {
+                    layers.extend(parent.iter().cloned());
                 }
             }
             if let Some(global) = self.sources.get(&Some(lang.clone())) {
-                for src in global {
-                    bundle.add_resource_overriding(src.to_owned());
-                }
+                layers.extend(global.iter().cloned());
             }
-            bundles.insert(lang, bundle);
+            resources.insert(lang, layers);
         }
 
         if self.errors.is_empty() {
-            Ok(FluentMachineBuilder {
-                bundles,
-                ..Default::default()
-            })
+            let mut builder = FluentMachineBuilder::from_resources(resources);
+            builder.diagnostics = self.diagnostics;
+            Ok(builder)
         } else {
-            Err((
-                FluentMachineBuilder {
-                    bundles,
-                    ..Default::default()
-                },
-                self.errors,
-            ))
+            Err((FluentMachineBuilder::from_resources(resources), self.errors))
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::FluentMachine;
+    use crate::{Error, FluentMachine};
 
     use super::{
-        FluentMachineInheritanceBuilder, InheritanceSyntaxErrorHandling, LanguageIdentifier,
+        parent_locales, FluentMachineInheritanceBuilder, InheritanceSyntaxErrorHandling,
+        LanguageIdentifier,
     };
     #[test]
     fn groups_by_locales() {
@@ -506,4 +620,193 @@ football = { -football-term } is the biggest North American sport, with Super Bo
             "American Football is the biggest North American sport, with Super Bowl 112.3 million viewers."
         );
     }
+
+    #[test]
+    fn parents_by_variant_region_and_script() {
+        let parents = |l: &str| {
+            parent_locales(&l.parse().unwrap())
+                .iter()
+                .map(|p| p.to_string())
+                .collect::<Vec<String>>()
+        };
+        assert_eq!(parents("zh-Hant-TW"), vec!["zh-Hant", "zh"]);
+        assert_eq!(parents("ca-ES-valencia"), vec!["ca-ES", "ca"]);
+        assert_eq!(
+            parents("sr-Latn-RS-ekavsk"),
+            vec!["sr-Latn-RS", "sr-Latn", "sr"]
+        );
+        assert_eq!(parents("en-US"), vec!["en"]);
+        assert!(parents("en").is_empty());
+    }
+
+    #[test]
+    fn inherits_multiple_levels() {
+        let i18n = FluentMachine::build_with_inheritance(InheritanceSyntaxErrorHandling::AtBuild)
+            .add_source("", "brand = Example", None)
+            .expect("Should add global")
+            .add_source(
+                "sr",
+                "language = Српски\nscript = Ћирилица\nregion = Међународни",
+                None,
+            )
+            .expect("Should add sr")
+            .add_source("sr-Latn", "script = Latinica\nregion = Međunarodni", None)
+            .expect("Should add sr-Latn")
+            .add_source("sr-Latn-RS", "region = Srbija", None)
+            .expect("Should add sr-Latn-RS")
+            .add_source(
+                "ca",
+                "language = Català\nregion = Internacional\nvariant = Central",
+                None,
+            )
+            .expect("Should add ca")
+            .add_source("ca-ES-valencia", "variant = Valencià", None)
+            .expect("Should add ca-ES-valencia")
+            .build_inheritance()
+            .expect("Should build")
+            .set_fallback_locale("sr")
+            .expect("Should set sr")
+            .finish()
+            .unwrap();
+        let t = |locale: &str, key: &str| {
+            let locale: LanguageIdentifier = locale.parse().unwrap();
+            i18n.t(&[&locale], key.try_into().unwrap(), None)
+        };
+        assert_eq!(t("sr-Latn-RS", "brand"), "Example");
+        assert_eq!(t("sr-Latn-RS", "language"), "Српски");
+        assert_eq!(t("sr-Latn-RS", "script"), "Latinica");
+        assert_eq!(t("sr-Latn-RS", "region"), "Srbija");
+        assert_eq!(t("sr-Latn", "region"), "Međunarodni");
+        assert_eq!(t("ca-ES-valencia", "language"), "Català");
+        assert_eq!(t("ca-ES-valencia", "region"), "Internacional");
+        assert_eq!(t("ca-ES-valencia", "variant"), "Valencià");
+    }
+
+    #[test]
+    fn declared_parents() {
+        let i18n = FluentMachine::build_with_inheritance(InheritanceSyntaxErrorHandling::AtBuild)
+            .add_source("", "brand = Example", None)
+            .expect("Should add global")
+            .add_source("pt", "bus = Ônibus\nteam = Time\nhello = Olá", None)
+            .expect("Should add pt")
+            .add_source("pt-PT", "bus = Autocarro\nteam = Equipa", None)
+            .expect("Should add pt-PT")
+            .add_source("pt-AO", "bus = Machimbombo", None)
+            .expect("Should add pt-AO")
+            .add_source("pt-MZ", "", None)
+            .expect("Should add pt-MZ")
+            .set_parent("pt-AO", "pt-PT")
+            .expect("Should set pt-AO parent")
+            .set_parent("pt-MZ", "pt-AO")
+            .expect("Should set pt-MZ parent")
+            .build_inheritance()
+            .expect("Should build")
+            .set_fallback_locale("pt")
+            .expect("Should set pt")
+            .finish()
+            .unwrap();
+        let t = |locale: &str, key: &str| {
+            let locale: LanguageIdentifier = locale.parse().unwrap();
+            i18n.t(&[&locale], key.try_into().unwrap(), None)
+        };
+        assert_eq!(t("pt-MZ", "bus"), "Machimbombo");
+        assert_eq!(t("pt-MZ", "team"), "Equipa");
+        assert_eq!(t("pt-MZ", "hello"), "Olá");
+        assert_eq!(t("pt-MZ", "brand"), "Example");
+        assert_eq!(t("pt-AO", "team"), "Equipa");
+        assert_eq!(t("pt-PT", "bus"), "Autocarro");
+    }
+
+    #[test]
+    fn rejects_parent_cycles() {
+        let builder = FluentMachineInheritanceBuilder::new(InheritanceSyntaxErrorHandling::AtBuild)
+            .set_parent("pt-AO", "pt-PT")
+            .unwrap()
+            .set_parent("pt-PT", "pt-MZ")
+            .unwrap();
+        let err = builder
+            .set_parent("pt-MZ", "pt-AO")
+            .err()
+            .expect("Should detect the cycle");
+        assert!(
+            matches!(&err, Error::InheritanceCycle(cycle) if cycle.iter().map(|l| l.to_string()).collect::<Vec<_>>() == ["pt-MZ", "pt-AO", "pt-PT", "pt-MZ"]),
+            "{err:?}"
+        );
+        assert!(matches!(
+            FluentMachineInheritanceBuilder::new(InheritanceSyntaxErrorHandling::AtBuild)
+                .set_parent("pt", "pt"),
+            Err(Error::InheritanceCycle(_))
+        ));
+        assert!(
+            matches!(
+                FluentMachineInheritanceBuilder::new(InheritanceSyntaxErrorHandling::AtBuild)
+                    .set_parent("pt", "pt-PT"),
+                Err(Error::InheritanceCycle(_))
+            ),
+            "pt-PT derives from pt"
+        );
+    }
+
+    #[test]
+    fn recovers_dropping_broken_entries() {
+        let builder =
+            FluentMachine::build_with_inheritance(InheritanceSyntaxErrorHandling::Recover)
+                .add_source("", "brand = Example", None)
+                .expect("Should add global")
+                .add_source(
+                    "pt",
+                    "hello = Olá\nemails = { $count ->\n   *[other] emails\n}\nbye = Adeus",
+                    None,
+                )
+                .expect("Should add pt")
+                .add_source(
+                    "pt-PT",
+                    r#"
+hello = Olá { $name
+emails = { $count ->
+    [one] Um email
+}
+bye = Até logo
+"#,
+                    Some("pt-PT/main.ftl"),
+                )
+                .expect("Should recover pt-PT")
+                .build_inheritance()
+                .expect("Should not return errors");
+        assert_eq!(
+            builder
+                .diagnostics()
+                .iter()
+                .map(|d| d.id.as_deref())
+                .collect::<Vec<_>>(),
+            vec![Some("hello"), Some("emails")]
+        );
+        let pt: LanguageIdentifier = "pt-PT".parse().unwrap();
+        let pt_bundle = &builder.bundles[&pt];
+        assert!(pt_bundle.has_message("bye"));
+        assert!(pt_bundle.has_message("brand"));
+
+        let i18n = builder
+            .set_fallback_locale("pt")
+            .expect("Should set pt")
+            .finish()
+            .unwrap();
+        let t = |key: &str| i18n.t(&[&pt], key.try_into().unwrap(), None);
+        assert_eq!(t("hello"), "Olá", "uses the parent version");
+        assert_eq!(t("emails"), "emails", "uses the parent version");
+        assert_eq!(t("bye"), "Até logo", "keeps valid entries");
+        assert_eq!(t("brand"), "Example");
+
+        let diagnostics = i18n.diagnostics();
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].locale, Some(pt.clone()));
+        assert_eq!(diagnostics[0].origin.as_deref(), Some("pt-PT/main.ftl"));
+        assert_eq!(diagnostics[0].id.as_deref(), Some("hello"));
+        assert_eq!(diagnostics[0].line, 2);
+        assert_eq!(diagnostics[1].id.as_deref(), Some("emails"));
+        assert_eq!(diagnostics[1].line, 3);
+        assert!(diagnostics[1]
+            .to_string()
+            .starts_with("`pt-PT/main.ftl` line 3: "));
+    }
 }
@@ -1,3 +1,4 @@
+use ahash::RandomState;
 use fluent_bundle::{FluentArgs, FluentResource, FluentValue};
 use std::{
     collections::{hash_map::Entry, HashMap},
@@ -6,7 +7,14 @@ use std::{
 };
 use unic_langid::LanguageIdentifier;
 
-use crate::{machine::MachineBundles, Error, FluentMachine, MachineBundle, NegotiationStrategy};
+use crate::{
+    args::DefaultArgs,
+    cache::NegotiationCache,
+    error::Diagnostic,
+    functions,
+    machine::{FormatErrorFn, FunctionFn, MachineBundles, MachineResources, MissingKeyPolicy},
+    Error, FluentMachine, MachineBundle, NegotiationStrategy,
+};
 
 /// Configure [`FluentMachine`] options negotiation strategy, default locale, add functions
 /// to locales and add a resource to a specific locale.
@@ -59,11 +67,24 @@ use crate::{machine::MachineBundles, Error, FluentMachine, MachineBundle, Negoti
 /// );
 ///
 /// ```
-
 pub struct FluentMachineBuilder {
     pub(crate) bundles: MachineBundles,
+    pub(crate) resources: MachineResources,
     pub(crate) strategy: NegotiationStrategy,
     pub(crate) fallback: LanguageIdentifier,
+    pub(crate) missing_key: MissingKeyPolicy,
+    pub(crate) negotiation_cache: Option<usize>,
+    pub(crate) format_error_handler: Option<FormatErrorFn>,
+    /// functions by name, for every locale or a single locale
+    pub(crate) functions: Vec<(Option<LanguageIdentifier>, String, FunctionFn)>,
+    pub(crate) default_args: Option<DefaultArgs>,
+    pub(crate) strict: bool,
+    /// requested locale to available locale
+    pub(crate) aliases: HashMap<LanguageIdentifier, LanguageIdentifier, RandomState>,
+    /// locales searched after the negotiated locale
+    pub(crate) fallback_chains: HashMap<LanguageIdentifier, Vec<LanguageIdentifier>, RandomState>,
+    /// entries dropped by the inheritance builder
+    pub(crate) diagnostics: Vec<Diagnostic>,
     #[cfg(feature = "actix-web4")]
     pub(crate) fallback_string: String,
     #[cfg(feature = "actix-web4")]
@@ -84,6 +105,25 @@ impl Debug for FluentMachineBuilder {
             )
             .field("strategy", &self.strategy)
             .field("fallback", &self.fallback)
+            .field("missing_key", &self.missing_key)
+            .field("negotiation_cache", &self.negotiation_cache)
+            .field("format_error_handler", &self.format_error_handler.is_some())
+            .field(
+                "functions",
+                &self
+                    .functions
+                    .iter()
+                    .map(|(locale, name, _)| match locale {
+                        Some(locale) => format!("{name}@{locale}"),
+                        None => name.clone(),
+                    })
+                    .collect::<Vec<String>>(),
+            )
+            .field("default_args", &self.default_args)
+            .field("strict", &self.strict)
+            .field("aliases", &self.aliases)
+            .field("fallback_chains", &self.fallback_chains)
+            .field("diagnostics", &self.diagnostics.len())
             .finish()
     }
 }
@@ -92,8 +132,18 @@ impl Default for FluentMachineBuilder {
     fn default() -> Self {
         Self {
             bundles: HashMap::default(),
+            resources: HashMap::default(),
             strategy: NegotiationStrategy::Filtering,
             fallback: "en".parse::<LanguageIdentifier>().unwrap(),
+            missing_key: MissingKeyPolicy::default(),
+            negotiation_cache: None,
+            format_error_handler: None,
+            functions: Vec::new(),
+            default_args: None,
+            strict: false,
+            aliases: HashMap::default(),
+            fallback_chains: HashMap::default(),
+            diagnostics: Vec::new(),
             #[cfg(feature = "actix-web4")]
             cookie_name: None,
             #[cfg(feature = "actix-web4")]
@@ -103,6 +153,25 @@ impl Default for FluentMachineBuilder {
 }
 
 impl FluentMachineBuilder {
+    /// Creates a bundle for each locale, adding the resources in order, overriding entries.
+    pub(crate) fn from_resources(resources: MachineResources) -> Self {
+        let bundles = resources
+            .iter()
+            .map(|(locale, xs)| {
+                let mut bundle: MachineBundle = MachineBundle::new_concurrent(vec![locale.clone()]);
+                for r in xs {
+                    bundle.add_resource_overriding(Arc::clone(r));
+                }
+                (locale.clone(), bundle)
+            })
+            .collect();
+        Self {
+            bundles,
+            resources,
+            ..Default::default()
+        }
+    }
+
     /// Set [`FluentMachine::negotiate_languages`] [`NegotiationStrategy`].
     ///
     /// # Arguments:
@@ -114,6 +183,201 @@ impl FluentMachineBuilder {
         self
     }
 
+    /// Set what [`FluentMachine::t`] returns when no negotiated locale has the key.
+    ///
+    /// # Arguments:
+    /// * `policy` applied to missing keys
+    ///
+    /// Default [`MissingKeyPolicy::Key`]
+    pub fn set_missing_key_policy(mut self, policy: MissingKeyPolicy) -> Self {
+        self.missing_key = policy;
+        self
+    }
+
+    /// Cache [`FluentMachine::negotiate_languages`] results by the raw requested string.
+    ///
+    /// # Arguments:
+    /// * `capacity` maximum cached requested strings, the oldest is evicted when full
+    ///
+    /// Default without cache
+    pub fn set_negotiation_cache(mut self, capacity: usize) -> Self {
+        self.negotiation_cache = Some(capacity);
+        self
+    }
+
+    /// Set a handler receiving every formatting error, with the key and the locale.
+    ///
+    /// # Arguments:
+    /// * `handler` called when formatting a message collects errors, like a missing `$variable`
+    ///
+    /// Default without handler, errors are only logged at debug level
+    pub fn set_format_error_handler(mut self, handler: FormatErrorFn) -> Self {
+        self.format_error_handler = Some(handler);
+        self
+    }
+
+    /// Set arguments passed to every translation, merged under the caller arguments.
+    ///
+    /// # Arguments:
+    /// * `args` shared arguments, like `$appName`, the caller values win on the same name,
+    ///   custom values are skipped, they can't be shared between threads
+    ///
+    /// The default arguments are merged with the caller arguments into new arguments on every
+    /// call, borrowing the default strings.
+    ///
+    /// Default without arguments
+    ///
+    /// # Example
+    /// ```rust
+    /// use fi18n::{f_args, FluentMachine, LanguageIdentifier};
+    ///
+    /// let i18n = FluentMachine::build()
+    ///     .add_resource("en", r#"welcome = Welcome to { $appName }, { $name }!"#)
+    ///     .expect("Should add en")
+    ///     .set_default_args(&f_args!["appName" => "Acme", "name" => "guest"])
+    ///     .finish()
+    ///     .expect("Should finish");
+    ///
+    /// let en: LanguageIdentifier = "en".parse().unwrap();
+    /// assert_eq!(
+    ///     i18n.t(&[&en], "welcome".try_into().unwrap(), None),
+    ///     "Welcome to \u{2068}Acme\u{2069}, \u{2068}guest\u{2069}!"
+    /// );
+    /// assert_eq!(
+    ///     i18n.t(&[&en], "welcome".try_into().unwrap(), Some(&f_args!["name" => "Ana"])),
+    ///     "Welcome to \u{2068}Acme\u{2069}, \u{2068}Ana\u{2069}!"
+    /// );
+    /// ```
+    pub fn set_default_args(mut self, args: &FluentArgs) -> Self {
+        self.default_args = Some(DefaultArgs::new(args));
+        self
+    }
+
+    /// Add an alias of an available locale, requested locales are replaced by the target
+    /// before [`FluentMachine::negotiate_languages`], in [`FluentMachine::localize_t`] and
+    /// cookie values.
+    ///
+    /// # Arguments:
+    /// * `alias` requested locale, like `en-GB`
+    /// * `target` available locale, like `en-UK`, must be present at finish
+    ///
+    /// ### Errors
+    ///
+    /// Returns [`Error::Overriding`] if `alias` was already added.
+    ///
+    /// # Example
+    /// ```rust
+    /// use fi18n::{FluentMachine, LanguageIdentifier};
+    ///
+    /// let i18n = FluentMachine::build()
+    ///     .add_resource("en", "region = International").expect("Should add en")
+    ///     .add_resource("en-UK", "region = United Kingdom").expect("Should add en-UK")
+    ///     .add_alias("en-GB", "en-UK").expect("Should add alias")
+    ///     .finish().expect("Should finish");
+    ///
+    /// assert_eq!(
+    ///     i18n.negotiate_languages("en-GB"),
+    ///     vec![
+    ///         &"en-UK".parse::<LanguageIdentifier>().unwrap(),
+    ///         &"en".parse::<LanguageIdentifier>().unwrap()
+    ///     ]
+    /// );
+    /// assert_eq!(i18n.localize_t("en-GB").t("region".try_into().unwrap(), None), "United Kingdom");
+    /// ```
+    pub fn add_alias(self, alias: &str, target: &str) -> Result<Self, Error> {
+        let alias = alias.parse::<LanguageIdentifier>()?;
+        let target = target.parse::<LanguageIdentifier>()?;
+        self.insert_alias(alias, target)
+    }
+
+    /// Adds a parsed alias, check [`FluentMachineBuilder::add_alias`].
+    pub(crate) fn insert_alias(
+        mut self,
+        alias: LanguageIdentifier,
+        target: LanguageIdentifier,
+    ) -> Result<Self, Error> {
+        match self.aliases.entry(alias) {
+            Entry::Occupied(e) => Err(Error::Overriding(vec![e.key().to_string()])),
+            Entry::Vacant(e) => {
+                e.insert(target);
+                Ok(self)
+            }
+        }
+    }
+
+    /// Set the locales searched, in order, after `locale` when translating. The fallback
+    /// locale is always searched last.
+    ///
+    /// # Arguments:
+    /// * `locale` negotiated locale, must be present at finish
+    /// * `chain` locales searched after `locale`, must be present at finish
+    ///
+    /// Default without chains, the negotiated locales are searched, then the fallback locale
+    ///
+    /// ### Errors
+    ///
+    /// Returns [`Error::Overriding`] if a chain of `locale` was already set.
+    ///
+    /// # Example
+    /// ```rust
+    /// use fi18n::{FluentMachine, NegotiationStrategy};
+    ///
+    /// let i18n = FluentMachine::build()
+    ///     .add_resource("en", "bus = Bus\nhelp = Help").expect("Should add en")
+    ///     .add_resource("pt", "hello = Olá").expect("Should add pt")
+    ///     .add_resource("pt-PT", "bus = Autocarro").expect("Should add pt-PT")
+    ///     .add_resource("pt-BR", "bus = Ônibus\nsoccer = Futebol").expect("Should add pt-BR")
+    ///     .add_resource("pt-AO", "soccer = Futebol").expect("Should add pt-AO")
+    ///     .set_strategy(NegotiationStrategy::Lookup)
+    ///     .set_fallback_chain("pt-AO", &["pt-PT", "pt"]).expect("Should set chain")
+    ///     .finish().expect("Should finish");
+    ///
+    /// let ao = i18n.localize_t("pt-AO");
+    /// assert_eq!(ao.locales().len(), 1);
+    /// assert_eq!(ao.t("bus".try_into().unwrap(), None), "Autocarro");
+    /// assert_eq!(ao.t("hello".try_into().unwrap(), None), "Olá");
+    /// assert_eq!(ao.t("help".try_into().unwrap(), None), "Help");
+    /// ```
+    pub fn set_fallback_chain(mut self, locale: &str, chain: &[&str]) -> Result<Self, Error> {
+        let locale = locale.parse::<LanguageIdentifier>()?;
+        let chain = chain
+            .iter()
+            .map(|l| l.parse::<LanguageIdentifier>())
+            .collect::<Result<Vec<_>, _>>()?;
+        match self.fallback_chains.entry(locale) {
+            Entry::Occupied(e) => Err(Error::Overriding(vec![e.key().to_string()])),
+            Entry::Vacant(e) => {
+                e.insert(chain);
+                Ok(self)
+            }
+        }
+    }
+
+    /// Fail [`FluentMachineBuilder::finish`] with the problems found by
+    /// [`FluentMachine::validate`].
+    ///
+    /// # Arguments:
+    /// * `strict` validates undefined references, select expressions without default, missing
+    ///   keys and different `$variables` than the fallback locale
+    ///
+    /// Default `false`
+    ///
+    /// # Example
+    /// ```rust
+    /// use fi18n::{Error, FluentMachine};
+    ///
+    /// let building = FluentMachine::build()
+    ///     .add_resource("en", "hello = Hello { $name }").expect("Should add en")
+    ///     .add_resource("pt", "hello = Olá { $nome }").expect("Should add pt")
+    ///     .set_strict(true)
+    ///     .finish();
+    /// assert!(matches!(building, Err(Error::Validation(issues)) if issues.len() == 1));
+    /// ```
+    pub fn set_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
     /// Set default locale.
     ///
     /// # Arguments:
@@ -152,41 +416,93 @@ impl FluentMachineBuilder {
         self
     }
 
+    /// Returns the entries dropped by
+    /// [`InheritanceSyntaxErrorHandling::Recover`](crate::builders::InheritanceSyntaxErrorHandling::Recover),
+    /// also available at [`FluentMachine::diagnostics`] after finish.
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
     /// Finish building and returns [`FluentMachine`].
     ///
     /// ### Errors
     ///
-    /// Returns [`Error::LocaleUnavailable`] if fallback locale is not present.
+    /// Returns [`Error::LocaleUnavailable`] if fallback locale, the locale of a function
+    /// added with [`FluentMachineBuilder::add_function_for_locale`], an alias target added
+    /// with [`FluentMachineBuilder::add_alias`], or a locale of a fallback chain, is not present.
+    ///
+    /// Returns [`Error::Validation`] with every problem found, in strict mode, check
+    /// [`FluentMachineBuilder::set_strict`].
+    ///
+    /// Returns [`Error::Overriding`] with the names of functions added with
+    /// [`FluentMachineBuilder::add_function`] that collide with enabled
+    /// [`functions`](crate::functions).
+    ///
+    /// Adds the functions to every bundle, locale functions first, then functions added with
+    /// [`FluentMachineBuilder::add_function`] and the enabled [`functions`](crate::functions).
     pub fn finish(mut self) -> Result<FluentMachine, Error> {
         let mut available: Vec<LanguageIdentifier> = self.bundles.keys().cloned().collect();
         if !available.contains(&self.fallback) {
             return Err(Error::LocaleUnavailable(self.fallback));
         }
+        if let Some((Some(locale), _, _)) = self
+            .functions
+            .iter()
+            .find(|(locale, _, _)| locale.as_ref().is_some_and(|l| !available.contains(l)))
+        {
+            return Err(Error::LocaleUnavailable(locale.clone()));
+        }
+        if let Some(target) = self.aliases.values().find(|l| !available.contains(l)) {
+            return Err(Error::LocaleUnavailable(target.clone()));
+        }
+        if let Some(locale) = self
+            .fallback_chains
+            .iter()
+            .flat_map(|(locale, chain)| std::iter::once(locale).chain(chain))
+            .find(|l| !available.contains(l))
+        {
+            return Err(Error::LocaleUnavailable(locale.clone()));
+        }
 
-        if cfg!(feature = "with-title") {
-            for bundle in self.bundles.values_mut() {
+        for (locale, bundle) in self.bundles.iter_mut() {
+            // locale functions take precedence over functions with the same name
+            let locale_functions = self
+                .functions
+                .iter()
+                .filter(|(l, _, _)| l.as_ref() == Some(locale));
+            let shadowed = |name: &String| locale_functions.clone().any(|(_, n, _)| n == name);
+            for (_, name, func) in locale_functions.clone().chain(
+                self.functions
+                    .iter()
+                    .filter(|(l, name, _)| l.is_none() && !shadowed(name)),
+            ) {
+                let func = Arc::clone(func);
                 bundle
-                    .add_function("TITLE", |positional, _named| match positional {
-                        [FluentValue::String(s)] => {
-                            let mut c = s.chars();
-                            match c.next() {
-                                None => String::new(),
-                                Some(f) => f.to_uppercase().collect::<String>() + c.as_str(),
-                            }
-                            .into()
-                        }
-                        _ => FluentValue::Error,
-                    })
-                    .expect("Failed to add a function to the bundle.");
+                    .add_function(name, move |positional, named| func(positional, named))
+                    .map_err(|_| Error::Overriding(vec![name.clone()]))?;
             }
+            bundle.set_formatter(Some(crate::html::format_value));
+            functions::register(bundle)?;
+        }
+
+        // the fallback locale is searched after every chain
+        for (locale, chain) in self.fallback_chains.iter_mut() {
+            let mut seen = vec![locale.clone(), self.fallback.clone()];
+            chain.retain(|l| {
+                let first = !seen.contains(l);
+                seen.push(l.clone());
+                first
+            });
         }
 
         available.sort();
         available.shrink_to_fit();
         self.bundles.shrink_to_fit();
+        self.resources.shrink_to_fit();
 
-        Ok(FluentMachine {
+        let machine = FluentMachine {
             bundles: self.bundles,
+            resources: self.resources,
             available,
             fallback: self.fallback,
             #[cfg(feature = "actix-web4")]
@@ -194,24 +510,150 @@ impl FluentMachineBuilder {
             #[cfg(feature = "actix-web4")]
             cookie_name: self.cookie_name,
             strategy: self.strategy,
-        })
+            missing_key: self.missing_key,
+            negotiation_cache: self.negotiation_cache.map(NegotiationCache::new),
+            format_error_handler: self.format_error_handler,
+            default_args: self.default_args,
+            aliases: self.aliases,
+            fallback_chains: self.fallback_chains,
+            diagnostics: self.diagnostics,
+        };
+        if self.strict {
+            let issues = machine.validate();
+            if !issues.is_empty() {
+                return Err(Error::Validation(issues));
+            }
+        }
+        Ok(machine)
     }
 
-    /// Add a function to bundles.
+    /// Add a function to every bundle, including locales added later.
     ///
     /// # Arguments:
     /// * `name` function name id
-    /// * `func` FTL function
+    /// * `func` FTL function, closures can capture state
+    ///
+    /// ### Errors
+    ///
+    /// Returns [`Error::Overriding`] if a function with `name` was already added.
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::{collections::HashMap, sync::Arc};
+    /// use fi18n::{FluentMachine, FluentValue, LanguageIdentifier};
+    ///
+    /// let flags = Arc::new(HashMap::from([("new-checkout", true)]));
+    /// let i18n = FluentMachine::build()
+    ///     .add_resource("en", r#"
+    /// checkout = { FLAG("new-checkout") ->
+    ///     [on] Pay now
+    ///    *[off] Checkout
+    /// }"#)
+    ///     .expect("Should add en")
+    ///     .add_function("FLAG", move |positional, _| match positional {
+    ///         [FluentValue::String(s)] if flags.get(s.as_ref()) == Some(&true) => "on".into(),
+    ///         _ => "off".into(),
+    ///     })
+    ///     .expect("Should add function")
+    ///     .finish()
+    ///     .expect("Should finish");
+    ///
+    /// let en: LanguageIdentifier = "en".parse().unwrap();
+    /// assert_eq!(i18n.t(&[&en], "checkout".try_into().unwrap(), None), "Pay now");
+    /// ```
     ///
     /// _For more information how to add function, check [`FluentBundle::add_function`](crate::fluent-bundle::bundle::FluentBundle::add_function`)_.
-    pub fn add_function<F>(mut self, name: &str, func: &'static F) -> Result<Self, Error>
+    pub fn add_function<F>(self, name: &str, func: F) -> Result<Self, Error>
     where
         F: for<'a> Fn(&[FluentValue<'a>], &FluentArgs) -> FluentValue<'a> + Sync + Send + 'static,
     {
-        for bundle in self.bundles.values_mut() {
-            bundle.add_function(name, func)?;
-        }
+        self.push_function(None, name, Arc::new(func))
+    }
+
+    /// Add a function already shared as a [`FunctionFn`] to every bundle, check
+    /// [`FluentMachineBuilder::add_function`].
+    ///
+    /// ### Errors
+    ///
+    /// Returns [`Error::Overriding`] if a function with `name` was already added.
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::sync::Arc;
+    /// use fi18n::{machine::FunctionFn, FluentMachine, LanguageIdentifier};
+    ///
+    /// let brand: FunctionFn = Arc::new(|_, _| "Example".into());
+    /// let i18n = FluentMachine::build()
+    ///     .add_resource("en", "welcome = Welcome to { BRAND() }")
+    ///     .expect("Should add en")
+    ///     .add_shared_function("BRAND", Arc::clone(&brand))
+    ///     .expect("Should add function")
+    ///     .finish()
+    ///     .expect("Should finish");
+    ///
+    /// let en: LanguageIdentifier = "en".parse().unwrap();
+    /// assert_eq!(
+    ///     i18n.t(&[&en], "welcome".try_into().unwrap(), None),
+    ///     "Welcome to \u{2068}Example\u{2069}"
+    /// );
+    /// ```
+    pub fn add_shared_function(self, name: &str, func: FunctionFn) -> Result<Self, Error> {
+        self.push_function(None, name, func)
+    }
 
+    /// Add a function to a single locale, taking precedence over [`FluentMachineBuilder::add_function`].
+    ///
+    /// # Arguments:
+    /// * `locale` valid locale with optional 2 regional letter code, must be present at finish
+    /// * `name` function name id
+    /// * `func` FTL function, closures can capture state
+    ///
+    /// ### Errors
+    ///
+    /// Returns [`Error::Overriding`] if a function with `name` was already added to `locale`.
+    pub fn add_function_for_locale<F>(
+        self,
+        locale: &str,
+        name: &str,
+        func: F,
+    ) -> Result<Self, Error>
+    where
+        F: for<'a> Fn(&[FluentValue<'a>], &FluentArgs) -> FluentValue<'a> + Sync + Send + 'static,
+    {
+        let locale = locale.parse::<LanguageIdentifier>()?;
+        self.push_function(Some(locale), name, Arc::new(func))
+    }
+
+    /// Add a function already shared as a [`FunctionFn`] to a single locale, check
+    /// [`FluentMachineBuilder::add_function_for_locale`].
+    ///
+    /// ### Errors
+    ///
+    /// Returns [`Error::Overriding`] if a function with `name` was already added to `locale`.
+    pub fn add_shared_function_for_locale(
+        self,
+        locale: &str,
+        name: &str,
+        func: FunctionFn,
+    ) -> Result<Self, Error> {
+        let locale = locale.parse::<LanguageIdentifier>()?;
+        self.push_function(Some(locale), name, func)
+    }
+
+    fn push_function(
+        mut self,
+        locale: Option<LanguageIdentifier>,
+        name: &str,
+        func: FunctionFn,
+    ) -> Result<Self, Error> {
+        if self
+            .functions
+            .iter()
+            .any(|(l, n, _)| l == &locale && n == name)
+        {
+            return Err(Error::Overriding(vec![name.to_string()]));
+        }
+        self.functions.push((locale, name.to_string(), func));
         Ok(self)
     }
 
@@ -240,6 +682,10 @@ impl FluentMachineBuilder {
                 }
             })?,
         );
+        self.resources
+            .entry(locale.clone())
+            .or_default()
+            .push(Arc::clone(&r));
         if let Entry::Vacant(e) = self.bundles.entry(locale.clone()) {
             let mut bundle: MachineBundle = MachineBundle::new_concurrent(vec![locale]);
             bundle.add_resource_overriding(r);
@@ -284,9 +730,13 @@ impl FluentMachineBuilder {
             );
         }
         let bundle = self.bundles.get_mut(&locale).unwrap();
+        let r = Arc::new(r);
 
-        match bundle.add_resource(Arc::new(r)) {
-            Ok(()) => Ok(self),
+        match bundle.add_resource(Arc::clone(&r)) {
+            Ok(()) => {
+                self.resources.entry(locale).or_default().push(r);
+                Ok(self)
+            }
             Err(errs) => Err(Error::MultipleFluentError(errs)),
         }
     }
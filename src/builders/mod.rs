@@ -7,5 +7,6 @@ builders to simplify the configuration and generation of [`FluentMachine`](crate
 mod inheritance;
 mod machine_build;
 
+pub(crate) use inheritance::parent_locales;
 pub use inheritance::{FluentMachineInheritanceBuilder, InheritanceSyntaxErrorHandling};
 pub use machine_build::FluentMachineBuilder;
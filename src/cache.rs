@@ -0,0 +1,131 @@
+/*!
+Bounded cache of negotiated locales by requested languages
+*/
+
+use ahash::RandomState;
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+/// Hits and misses of [`FluentMachine::negotiate_languages`](crate::FluentMachine::negotiate_languages) cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct NegotiationCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl NegotiationCacheStats {
+    /// Ratio of hits to lookups, `0.0` without lookups.
+    pub fn hit_rate(&self) -> f64 {
+        match self.hits + self.misses {
+            0 => 0.0,
+            total => self.hits as f64 / total as f64,
+        }
+    }
+}
+
+#[derive(Default)]
+struct Entries {
+    map: HashMap<String, Arc<[usize]>, RandomState>,
+    // insertion order, oldest is evicted first
+    order: VecDeque<String>,
+}
+
+/// Stores the negotiated positions of available locales, by raw requested string.
+pub(crate) struct NegotiationCache {
+    capacity: usize,
+    entries: Mutex<Entries>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl NegotiationCache {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Mutex::new(Entries::default()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns cached positions or stores the result of `negotiate`.
+    pub(crate) fn get_or_insert_with<F>(&self, requested: &str, negotiate: F) -> Arc<[usize]>
+    where
+        F: FnOnce() -> Vec<usize>,
+    {
+        if let Some(found) = self.lock().map.get(requested) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return Arc::clone(found);
+        }
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let negotiated: Arc<[usize]> = negotiate().into();
+        if self.capacity == 0 {
+            return negotiated;
+        }
+
+        let mut entries = self.lock();
+        if !entries.map.contains_key(requested) {
+            if entries.order.len() >= self.capacity {
+                if let Some(oldest) = entries.order.pop_front() {
+                    entries.map.remove(&oldest);
+                }
+            }
+            entries.order.push_back(requested.to_string());
+            entries
+                .map
+                .insert(requested.to_string(), Arc::clone(&negotiated));
+        }
+        negotiated
+    }
+
+    pub(crate) fn stats(&self) -> NegotiationCacheStats {
+        NegotiationCacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn len(&self) -> usize {
+        self.lock().map.len()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Entries> {
+        // entries are always consistent, a poisoned lock is safe to reuse
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NegotiationCache;
+
+    #[test]
+    fn evicts_oldest_when_full() {
+        let cache = NegotiationCache::new(2);
+        cache.get_or_insert_with("en", || vec![0]);
+        cache.get_or_insert_with("pt", || vec![1]);
+        cache.get_or_insert_with("en", || unreachable!());
+        cache.get_or_insert_with("de", || vec![0]);
+        assert_eq!(cache.len(), 2);
+        assert_eq!(&*cache.get_or_insert_with("en", || vec![2]), &[2]);
+
+        let stats = cache.stats();
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.misses, 4);
+        assert_eq!(stats.hit_rate(), 0.2);
+    }
+
+    #[test]
+    fn zero_capacity_does_not_store() {
+        let cache = NegotiationCache::new(0);
+        cache.get_or_insert_with("en", || vec![0]);
+        assert_eq!(cache.len(), 0);
+        assert_eq!(cache.stats().misses, 1);
+    }
+}
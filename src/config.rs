@@ -0,0 +1,348 @@
+/*!
+Build [`FluentMachine`] from a declarative TOML or JSON manifest (requires features = ["config"])
+
+```toml
+# directories loaded with the inheritance loader, relative to the manifest
+roots = ["locales/"]
+# default `en`
+fallback = "en-US"
+# `filtering` (default), `matching` or `lookup`
+strategy = "filtering"
+# `at-add-source`, `at-build` (default) or `recover`
+error_handling = "recover"
+# optional, loaded locales by default
+locales = ["en", "en-US", "pt"]
+# optional, requires features = ["actix-web4"], ignored with a warning otherwise
+cookie_name = "lang"
+# optional, check `FluentMachineBuilder::set_strict`
+strict = false
+
+[aliases]
+en-GB = "en-US"
+```
+
+The same keys are accepted in a `.json` manifest.
+*/
+
+use serde::Deserialize;
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    builders::{FluentMachineBuilder, InheritanceSyntaxErrorHandling},
+    Error, FluentMachine, LanguageIdentifier, NegotiationStrategy,
+};
+
+/// Manifest keys
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Manifest {
+    roots: Vec<PathBuf>,
+    fallback: Option<String>,
+    strategy: Option<String>,
+    error_handling: Option<String>,
+    locales: Option<Vec<String>>,
+    cookie_name: Option<String>,
+    strict: Option<bool>,
+    #[serde(default)]
+    aliases: BTreeMap<String, String>,
+}
+
+fn invalid(key: impl Into<String>, message: impl Into<String>) -> Error {
+    Error::Config {
+        key: Some(key.into()),
+        message: message.into(),
+    }
+}
+
+impl Manifest {
+    /// Parses `content` according to the `path` extension.
+    fn parse(path: &Path, content: &str) -> Result<Self, Error> {
+        let parsed = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => toml::from_str(content).map_err(|e| e.to_string()),
+            Some("json") => serde_json::from_str(content).map_err(|e| e.to_string()),
+            _ => Err("expected a `.toml` or `.json` file".to_string()),
+        };
+        parsed.map_err(|message| Error::Config { key: None, message })
+    }
+
+    /// Loads the roots, relative to `base`, and configures the builder.
+    fn builder(self, base: &Path) -> Result<FluentMachineBuilder, Error> {
+        let mode = match self.error_handling.as_deref() {
+            Some("at-add-source") => InheritanceSyntaxErrorHandling::AtAddSource,
+            None | Some("at-build") => InheritanceSyntaxErrorHandling::AtBuild,
+            Some("recover") => InheritanceSyntaxErrorHandling::Recover,
+            Some(other) => {
+                return Err(invalid(
+                    "error_handling",
+                    format!("unknown `{other}`, expected `at-add-source`, `at-build` or `recover`"),
+                ))
+            }
+        };
+        let strategy = match self.strategy.as_deref() {
+            None | Some("filtering") => NegotiationStrategy::Filtering,
+            Some("matching") => NegotiationStrategy::Matching,
+            Some("lookup") => NegotiationStrategy::Lookup,
+            Some(other) => {
+                return Err(invalid(
+                    "strategy",
+                    format!("unknown `{other}`, expected `filtering`, `matching` or `lookup`"),
+                ))
+            }
+        };
+        if self.roots.is_empty() {
+            return Err(invalid("roots", "expected at least one directory"));
+        }
+
+        let mut inheritance = FluentMachine::build_with_inheritance(mode);
+        for (i, root) in self.roots.iter().enumerate() {
+            let key = format!("roots[{i}]");
+            let root = base.join(root);
+            if !root.is_dir() {
+                return Err(invalid(
+                    key,
+                    format!("`{}` is not a directory", root.display()),
+                ));
+            }
+            let root = root
+                .to_str()
+                .ok_or_else(|| invalid(&key, "expected an UTF-8 path"))?;
+            inheritance = inheritance.load_locales(root)?;
+        }
+        let mut builder = match inheritance.build_inheritance() {
+            Ok(builder) => builder,
+            Err((_, mut errors)) => {
+                for err in errors.iter().skip(1) {
+                    log::warn!(target: "config", "{err}");
+                }
+                return Err(errors.remove(0).into());
+            }
+        };
+
+        if let Some(locales) = self.locales {
+            let mut enabled = Vec::with_capacity(locales.len());
+            for (i, locale) in locales.iter().enumerate() {
+                let key = format!("locales[{i}]");
+                let locale = locale
+                    .parse::<LanguageIdentifier>()
+                    .map_err(|e| invalid(&key, e.to_string()))?;
+                if !builder.bundles.contains_key(&locale) {
+                    return Err(invalid(
+                        key,
+                        format!("locale `{locale}` has no translations"),
+                    ));
+                }
+                enabled.push(locale);
+            }
+            builder.bundles.retain(|l, _| enabled.contains(l));
+            builder.resources.retain(|l, _| enabled.contains(l));
+        }
+
+        builder = builder.set_strategy(strategy);
+        if let Some(fallback) = self.fallback {
+            builder = builder
+                .set_fallback_locale(&fallback)
+                .map_err(|e| invalid("fallback", e.to_string()))?;
+        }
+        if !builder.bundles.contains_key(&builder.fallback) {
+            return Err(invalid(
+                "fallback",
+                format!("locale `{}` is not available", builder.fallback),
+            ));
+        }
+        for (alias, target) in self.aliases.iter() {
+            let key = format!("aliases.{alias}");
+            let parse = |locale: &str| {
+                locale
+                    .parse::<LanguageIdentifier>()
+                    .map_err(|e| invalid(&key, e.to_string()))
+            };
+            let (alias, target) = (parse(alias)?, parse(target)?);
+            if !builder.bundles.contains_key(&target) {
+                return Err(invalid(key, format!("locale `{target}` is not available")));
+            }
+            builder = builder
+                .insert_alias(alias, target)
+                .map_err(|e| invalid(&key, e.to_string()))?;
+        }
+        if let Some(strict) = self.strict {
+            builder = builder.set_strict(strict);
+        }
+        if let Some(name) = self.cookie_name {
+            #[cfg(feature = "actix-web4")]
+            {
+                builder = builder.set_cookie_name(&name);
+            }
+            #[cfg(not(feature = "actix-web4"))]
+            log::warn!(target: "config", "`cookie_name` `{name}` ignored, requires the `actix-web4` feature");
+        }
+        Ok(builder)
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "config")))]
+impl FluentMachine {
+    /// Build an [`FluentMachine`] from a TOML or JSON manifest, check [`config`](crate::config),
+    /// returning the [`FluentMachineBuilder`] to continue configuration, like adding functions.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if the manifest or a locale file can't be read.
+    ///
+    /// Returns [`Error::Config`] with the manifest key, if the manifest is invalid.
+    ///
+    /// Returns [`Error::FluentResourceError`] if a locale file has syntax errors, according to
+    /// `error_handling`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use fi18n::FluentMachine;
+    ///
+    /// let i18n = FluentMachine::build_config("examples/config/fi18n.toml")
+    ///     .expect("Should load config")
+    ///     .add_function("BRAND", |_, _| "Example".into())
+    ///     .expect("Should add function")
+    ///     .finish()
+    ///     .expect("Should finish");
+    /// let gb = i18n.localize_t("en-GB");
+    /// assert_eq!(gb.t("region".try_into().unwrap(), None), "United Kingdom");
+    /// ```
+    pub fn build_config<P: AsRef<Path>>(path: P) -> Result<FluentMachineBuilder, Error> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)?;
+        let base = path.parent().unwrap_or_else(|| Path::new(""));
+        Manifest::parse(path, &content)?.builder(base)
+    }
+
+    /// Build an [`FluentMachine`] from a TOML or JSON manifest, check
+    /// [`FluentMachine::build_config`].
+    pub fn from_config<P: AsRef<Path>>(path: P) -> Result<FluentMachine, Error> {
+        Self::build_config(path)?.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Manifest;
+    use crate::{Error, FluentMachine, LanguageIdentifier, NegotiationStrategy};
+    use std::path::Path;
+
+    fn build(file: &str, content: &str) -> Result<FluentMachine, Error> {
+        Manifest::parse(Path::new(file), content)?
+            .builder(Path::new("examples/config"))?
+            .finish()
+    }
+
+    fn config_key(result: Result<FluentMachine, Error>) -> Option<String> {
+        match result {
+            Err(Error::Config { key, .. }) => key,
+            Err(e) => panic!("Should be a config error, found {e:?}"),
+            Ok(_) => panic!("Should be a config error"),
+        }
+    }
+
+    #[test]
+    fn toml_and_json_manifests() {
+        for path in ["examples/config/fi18n.toml", "examples/config/fi18n.json"] {
+            let i18n = FluentMachine::from_config(path).expect("Should build");
+            assert_eq!(
+                i18n.get_fallback_locale(),
+                &"en-US".parse::<LanguageIdentifier>().unwrap()
+            );
+            assert_eq!(i18n.get_strategy(), NegotiationStrategy::Filtering);
+            assert_eq!(
+                i18n.get_supported_locales()
+                    .iter()
+                    .map(|l| l.to_string())
+                    .collect::<Vec<String>>(),
+                vec!["en", "en-UK", "en-US", "pt", "pt-PT"],
+                "{path} enables locales"
+            );
+            let gb = i18n.localize_t("en-GB");
+            assert_eq!(gb.t("region".try_into().unwrap(), None), "United Kingdom");
+            let pt = i18n.localize_t("pt-AO");
+            assert_eq!(pt.primary_locale().unwrap().to_string(), "pt-PT");
+        }
+    }
+
+    #[test]
+    fn errors_point_to_key() {
+        assert_eq!(
+            config_key(build(
+                "fi18n.toml",
+                "roots = [\"../locales\"]\nstrategy = \"closest\""
+            )),
+            Some("strategy".into())
+        );
+        assert_eq!(
+            config_key(build(
+                "fi18n.json",
+                r#"{"roots": ["../locales"], "error_handling": "ignore"}"#
+            )),
+            Some("error_handling".into())
+        );
+        assert_eq!(
+            config_key(build(
+                "fi18n.toml",
+                "roots = [\"../locales\", \"../missing\"]"
+            )),
+            Some("roots[1]".into())
+        );
+        assert_eq!(
+            config_key(build(
+                "fi18n.toml",
+                "roots = [\"../locales\"]\nlocales = [\"en\", \"de\"]"
+            )),
+            Some("locales[1]".into())
+        );
+        assert_eq!(
+            config_key(build(
+                "fi18n.toml",
+                "roots = [\"../locales\"]\nlocales = [\"pt\"]\nfallback = \"en\""
+            )),
+            Some("fallback".into())
+        );
+        assert_eq!(
+            config_key(build(
+                "fi18n.toml",
+                "roots = [\"../locales\"]\nfallback = \"en\"\n[aliases]\nen-GB = \"en-IE\""
+            )),
+            Some("aliases.en-GB".into())
+        );
+        let err = build("fi18n.toml", "roots = [\"../locales\"]\nfallbak = \"en\"")
+            .err()
+            .expect("Should fail");
+        assert!(
+            matches!(&err, Error::Config { key: None, message } if message.contains("fallbak")),
+            "{err}"
+        );
+        assert!(matches!(
+            build("fi18n.yaml", "roots: []"),
+            Err(Error::Config { key: None, .. })
+        ));
+        assert_eq!(
+            config_key(build(
+                "fi18n.toml",
+                "roots = [\"../locales\"]\nfallback = \"en\"\n[aliases]\n\"en_@\" = \"en-US\""
+            )),
+            Some("aliases.en_@".into())
+        );
+        assert!(build(
+            "fi18n.toml",
+            "roots = [\"../locales\"]\nfallback = \"en-US\""
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn cookie_name_is_optional() {
+        assert!(build(
+            "fi18n.toml",
+            "roots = [\"../locales\"]\nfallback = \"en-US\"\ncookie_name = \"lang\""
+        )
+        .is_ok());
+    }
+}
@@ -0,0 +1,216 @@
+/*!
+Translation coverage of the available locales against the fallback locale
+*/
+
+use std::collections::BTreeSet;
+
+use crate::{FluentMachine, LanguageIdentifier};
+
+/// Coverage of every available locale, returned by [`FluentMachine::coverage`]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CoverageReport {
+    /// Locale used as reference
+    pub fallback: LanguageIdentifier,
+    /// Coverage of each locale, excluding the fallback, sorted by locale
+    pub locales: Vec<LocaleCoverage>,
+}
+
+impl CoverageReport {
+    /// Returns locales translated below `percentage`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use fi18n::FluentMachine;
+    ///
+    /// let i18n = FluentMachine::build()
+    ///     .add_resource("en", "hello = Hello\nbye = Bye").expect("Should add en")
+    ///     .add_resource("pt", "hello = Olá").expect("Should add pt")
+    ///     .finish().expect("Should finish");
+    ///
+    /// let report = i18n.coverage();
+    /// assert_eq!(report.locales[0].translated, 50.0);
+    /// assert_eq!(report.below(80.0).len(), 1, "CI should fail");
+    /// ```
+    pub fn below(&self, percentage: f64) -> Vec<&LocaleCoverage> {
+        self.locales
+            .iter()
+            .filter(|l| l.translated < percentage)
+            .collect()
+    }
+}
+
+/// Coverage of a locale, keys are formatted as `message` or `message.attribute`
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LocaleCoverage {
+    pub locale: LanguageIdentifier,
+    /// Fallback messages not present
+    pub missing_messages: Vec<String>,
+    /// Fallback attributes, or value, not present in messages that exist in both
+    pub missing_attributes: Vec<String>,
+    /// Messages and attributes that don't exist in the fallback
+    pub extra: Vec<String>,
+    /// Percentage of fallback keys present
+    pub translated: f64,
+}
+
+impl FluentMachine {
+    /// Compares every available locale keys against [`FluentMachine::get_fallback_locale`].
+    ///
+    /// Keys are the messages with value and each attribute. Inherited messages, from
+    /// [`build_inheritance`](crate::builders::FluentMachineInheritanceBuilder::build_inheritance)
+    /// or [`DirectoryLoader`](crate::loaders::DirectoryLoader), count as present in the locale.
+    pub fn coverage(&self) -> CoverageReport {
+        let reference = self.keys(&self.fallback);
+        let locales = self
+            .available
+            .iter()
+            .filter(|locale| **locale != self.fallback)
+            .map(|locale| {
+                let keys = self.keys(locale);
+                let messages: BTreeSet<&str> = self
+                    .message_ids(locale)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .collect();
+                let (missing_messages, missing_attributes): (Vec<String>, Vec<String>) = reference
+                    .difference(&keys)
+                    .cloned()
+                    .partition(|k| !messages.contains(k.split('.').next().unwrap_or(k)));
+                let present = reference.len() - missing_messages.len() - missing_attributes.len();
+                LocaleCoverage {
+                    locale: locale.clone(),
+                    missing_messages: dedup_messages(missing_messages),
+                    missing_attributes,
+                    extra: keys.difference(&reference).cloned().collect(),
+                    translated: if reference.is_empty() {
+                        100.0
+                    } else {
+                        present as f64 * 100.0 / reference.len() as f64
+                    },
+                }
+            })
+            .collect();
+
+        CoverageReport {
+            fallback: self.fallback.clone(),
+            locales,
+        }
+    }
+
+    // message with value and attribute keys of `locale`
+    fn keys(&self, locale: &LanguageIdentifier) -> BTreeSet<String> {
+        let mut keys = BTreeSet::new();
+        for id in self.message_ids(locale).unwrap_or_default() {
+            if let Some(msg) = self.message_entry(locale, id) {
+                if msg.value.is_some() {
+                    keys.insert(id.to_string());
+                }
+                for attr in msg.attributes.iter() {
+                    keys.insert(format!("{id}.{}", attr.id.name));
+                }
+            }
+        }
+        keys
+    }
+}
+
+// missing keys of a message as the message id
+fn dedup_messages(keys: Vec<String>) -> Vec<String> {
+    keys.iter()
+        .map(|k| k.split('.').next().unwrap_or(k).to_string())
+        .collect::<BTreeSet<String>>()
+        .into_iter()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        builders::InheritanceSyntaxErrorHandling, loaders::DirectoryLoader, FluentMachine,
+        LanguageIdentifier,
+    };
+
+    #[test]
+    fn reports_missing_and_extra_keys() {
+        let i18n = FluentMachine::build_with_inheritance(InheritanceSyntaxErrorHandling::AtBuild)
+            .add_source(
+                "en",
+                r#"
+hello = Hello
+bye = Bye
+login = Login
+    .username = Username
+    .password = Password
+"#,
+                None,
+            )
+            .expect("Should add en")
+            .add_source("en-US", "hello = Hi", None)
+            .expect("Should add en-US")
+            .add_source(
+                "pt",
+                r#"
+hello = Olá
+login = Entrar
+    .username = Utilizador
+    .email = Email
+extra = Extra
+"#,
+                None,
+            )
+            .expect("Should add pt")
+            .build_inheritance()
+            .expect("Should build")
+            .finish()
+            .expect("Should finish");
+        let report = i18n.coverage();
+
+        assert_eq!(report.fallback, "en".parse::<LanguageIdentifier>().unwrap());
+        assert_eq!(report.locales.len(), 2);
+        let us = &report.locales[0];
+        assert_eq!(us.locale, "en-US".parse::<LanguageIdentifier>().unwrap());
+        assert_eq!(us.translated, 100.0, "inherits from en");
+        let pt = &report.locales[1];
+        assert_eq!(pt.missing_messages, vec!["bye"]);
+        assert_eq!(pt.missing_attributes, vec!["login.password"]);
+        assert_eq!(pt.extra, vec!["extra", "login.email"]);
+        assert_eq!(pt.translated, 60.0);
+        assert_eq!(report.below(100.0).len(), 1);
+    }
+
+    #[test]
+    fn directory_loader_coverage() {
+        let i18n = FluentMachine::build_loader(DirectoryLoader::new("examples/locales/"))
+            .unwrap()
+            .set_fallback_locale("en-US")
+            .expect("failed to parse locale")
+            .finish()
+            .expect("failed to create FluentMachine");
+        let report = i18n.coverage();
+        let uk = report
+            .locales
+            .iter()
+            .find(|l| l.locale == "en-UK".parse::<LanguageIdentifier>().unwrap())
+            .unwrap();
+        assert_eq!(uk.translated, 100.0);
+        assert!(uk.extra.is_empty());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn report_is_serializable() {
+        let i18n = FluentMachine::build()
+            .add_resource("en", "hello = Hello")
+            .expect("Should add en")
+            .add_resource("pt", "bye = Adeus")
+            .expect("Should add pt")
+            .finish()
+            .expect("Should finish");
+        assert_eq!(
+            serde_json::to_string(&i18n.coverage()).unwrap(),
+            r#"{"fallback":"en","locales":[{"locale":"pt","missing_messages":["hello"],"missing_attributes":[],"extra":["bye"],"translated":0.0}]}"#
+        );
+    }
+}
@@ -50,6 +50,75 @@ impl fmt::Display for FluentResourceError {
 
 impl std::error::Error for FluentResourceError {}
 
+/// Entry dropped by [`InheritanceSyntaxErrorHandling::Recover`](crate::builders::InheritanceSyntaxErrorHandling::Recover),
+/// returned by [`FluentMachine::diagnostics`](crate::FluentMachine::diagnostics)
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Diagnostic {
+    /// Locale of the source, `None` if shared between all locales
+    pub locale: Option<unic_langid::LanguageIdentifier>,
+    /// Origin of the source, like the file path
+    pub origin: Option<String>,
+    /// Message, or term, id of the dropped entry, if recognizable
+    pub id: Option<String>,
+    /// First line of the dropped entry
+    pub line: usize,
+    /// Syntax error
+    pub error: String,
+    /// Source of the dropped entry
+    pub content: String,
+}
+
+impl Diagnostic {
+    /// Diagnostic of each syntax error of `ftl`.
+    pub(crate) fn from_errors(
+        locale: Option<&unic_langid::LanguageIdentifier>,
+        ftl: &str,
+        origin: Option<&str>,
+        errs: &[ParserError],
+    ) -> Vec<Self> {
+        errs.iter()
+            .map(|ParserError { slice, kind, pos }| {
+                let rg = slice.clone().unwrap_or_else(|| pos.clone());
+                let content = &ftl[rg.start..cmp::min(rg.end, ftl.len())];
+                Self {
+                    locale: locale.cloned(),
+                    origin: origin.map(|s| s.to_string()),
+                    id: content
+                        .split_once('=')
+                        .map(|(id, _)| id.trim())
+                        .filter(|id| {
+                            !id.is_empty()
+                                && id
+                                    .trim_start_matches('-')
+                                    .chars()
+                                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+                        })
+                        .map(|id| id.to_string()),
+                    line: ftl[..rg.start].matches('\n').count() + 1,
+                    error: kind.to_string(),
+                    content: content.to_string(),
+                }
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.origin, &self.locale) {
+            (Some(origin), _) => write!(f, "`{origin}`")?,
+            (None, Some(locale)) => write!(f, "{locale}")?,
+            (None, None) => write!(f, "global")?,
+        }
+        write!(f, " line {}: {}", self.line, self.error)?;
+        if let Some(id) = &self.id {
+            write!(f, ", dropped `{id}`")?;
+        }
+        Ok(())
+    }
+}
+
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
     #[error(transparent)]
@@ -73,8 +142,81 @@ pub enum Error {
     },
     #[error("probles {0:?}")]
     Overriding(Vec<String>),
+    #[error(
+        "Invalid config{}: {message}",
+        .key.as_ref().map(|k| format!(" `{k}`")).unwrap_or_default()
+    )]
+    Config {
+        /// Manifest key, `None` if the manifest can't be parsed
+        key: Option<String>,
+        message: String,
+    },
+    #[error("Invalid locale metadata `{path}` line {line}: {message}")]
+    LocaleMeta {
+        /// Path of the `locale.meta` file
+        path: String,
+        /// Line of the invalid entry, starting at 1
+        line: usize,
+        message: String,
+    },
+    #[error("Locale inheritance cycle {0:?}")]
+    InheritanceCycle(Vec<unic_langid::LanguageIdentifier>),
+    #[error("Strict validation failed {0:#?}")]
+    Validation(Vec<crate::validation::ValidationIssue>),
     #[error("Unexpected ")]
     Unexpected,
     #[error(transparent)]
     FluentResourceError(#[from] FluentResourceError),
 }
+
+/// Translation errors returned by [`FluentMachine::try_t`](crate::FluentMachine::try_t).
+///
+/// Every variant carries the locale chain that was searched.
+#[derive(thiserror::Error, Debug)]
+pub enum TranslateError {
+    #[error("Message `{id}` not found in locales [{}]", join_locales(.locales))]
+    MissingMessage {
+        id: String,
+        locales: Box<[unic_langid::LanguageIdentifier]>,
+    },
+    #[error("Attribute `{attribute}` of message `{id}` not found in locales [{}]", join_locales(.locales))]
+    MissingAttribute {
+        id: String,
+        attribute: String,
+        locales: Box<[unic_langid::LanguageIdentifier]>,
+    },
+    #[error("Message `{id}` has no value in locales [{}]", join_locales(.locales))]
+    MessageWithoutValue {
+        id: String,
+        locales: Box<[unic_langid::LanguageIdentifier]>,
+    },
+    #[error("Errors while formatting `{key}` in locale `{locale}`: {errors:?}")]
+    Format {
+        key: String,
+        locale: unic_langid::LanguageIdentifier,
+        locales: Box<[unic_langid::LanguageIdentifier]>,
+        errors: Vec<fluent_bundle::FluentError>,
+        /// Formatted output, including the placeholders of the failed parts
+        value: String,
+    },
+}
+
+impl TranslateError {
+    /// Locales searched while translating.
+    pub fn locales(&self) -> &[unic_langid::LanguageIdentifier] {
+        match self {
+            Self::MissingMessage { locales, .. }
+            | Self::MissingAttribute { locales, .. }
+            | Self::MessageWithoutValue { locales, .. }
+            | Self::Format { locales, .. } => locales,
+        }
+    }
+}
+
+fn join_locales(locales: &[unic_langid::LanguageIdentifier]) -> String {
+    locales
+        .iter()
+        .map(|l| l.to_string())
+        .collect::<Vec<String>>()
+        .join(", ")
+}
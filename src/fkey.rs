@@ -27,7 +27,7 @@ pub enum ParserError {
 /// assert_eq!(Fkey::try_from(""), Err(ParserError::Empty));
 /// assert_eq!(Fkey::try_from("a.b.c"), Err(ParserError::ToManyAttributes("a.b.c".into())));
 /// ```
-#[derive(PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Fkey<'a>(pub(super) &'a str, pub(super) Option<&'a str>);
 
 impl<'a> Fkey<'a> {
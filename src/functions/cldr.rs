@@ -0,0 +1,752 @@
+//! Locale data taken from CLDR, for the locales shipped with fi18n.
+
+// shared by the locale functions, unused tables depend on the enabled features
+#![cfg_attr(
+    not(all(
+        feature = "fn-currency",
+        feature = "fn-datetime",
+        feature = "fn-relative-time"
+    )),
+    allow(dead_code)
+)]
+
+use crate::LanguageIdentifier;
+
+/// Number symbols of a locale.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct NumberSymbols {
+    pub decimal: &'static str,
+    pub group: &'static str,
+    pub minus: &'static str,
+    /// placed after the number in percent style
+    pub percent: &'static str,
+}
+
+/// Currency placement and names of a locale.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct CurrencyFormat {
+    /// symbol placed before the number
+    pub before: bool,
+    /// between the symbol and the number
+    pub spacing: &'static str,
+    /// symbols overriding [`CURRENCIES`], `(code, symbol)`
+    pub symbols: &'static [(&'static str, &'static str)],
+    /// display names, `(code, one, other)`
+    pub names: &'static [(&'static str, &'static str, &'static str)],
+}
+
+/// Calendar names and date patterns of a locale.
+///
+/// Patterns use the CLDR letters, styles are ordered `full`, `long`, `medium`, `short`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct DateSymbols {
+    pub months: [&'static str; 12],
+    pub months_short: [&'static str; 12],
+    /// starting on sunday
+    pub weekdays: [&'static str; 7],
+    /// AM and PM
+    pub day_periods: [&'static str; 2],
+    pub date: [&'static str; 4],
+    pub time: [&'static str; 4],
+    /// joins the date `{1}` and time `{0}`, by date style
+    pub date_time: [&'static str; 4],
+}
+
+/// Relative time patterns of a locale, `{0}` is the formatted count.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct RelativeSymbols {
+    /// second to year, `[future one, future other, past one, past other]`
+    pub units: [[&'static str; 4]; 7],
+    /// last, this and next, for day, week, month and year
+    pub named: [[&'static str; 3]; 4],
+    pub now: &'static str,
+}
+
+/// Locale data used by the builtin formatters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct LocaleData {
+    pub number: NumberSymbols,
+    pub currency: CurrencyFormat,
+    pub date: DateSymbols,
+    pub relative: RelativeSymbols,
+}
+
+impl LocaleData {
+    /// Symbol of the ISO 4217 `code` in this locale.
+    pub fn currency_symbol(&self, code: &str) -> Option<&'static str> {
+        self.currency
+            .symbols
+            .iter()
+            .find(|(c, _)| *c == code)
+            .map(|(_, s)| *s)
+            .or_else(|| currency(code).map(|(s, _)| s))
+    }
+
+    /// Display name of the ISO 4217 `code` in this locale, `one` or `other` plural form.
+    pub fn currency_name(&self, code: &str, one: bool) -> Option<&'static str> {
+        self.currency
+            .names
+            .iter()
+            .find(|(c, _, _)| *c == code)
+            .map(|(_, o, other)| if one { *o } else { *other })
+    }
+}
+
+/// Currencies, `(code, symbol, fraction digits)`.
+const CURRENCIES: &[(&str, &str, usize)] = &[
+    ("AOA", "Kz", 2),
+    ("BRL", "R$", 2),
+    ("CHF", "CHF", 2),
+    ("EUR", "€", 2),
+    ("GBP", "£", 2),
+    ("JPY", "JP¥", 0),
+    ("MZN", "MTn", 2),
+    ("USD", "US$", 2),
+];
+
+/// Symbol and fraction digits of the ISO 4217 `code`.
+pub(crate) fn currency(code: &str) -> Option<(&'static str, usize)> {
+    CURRENCIES
+        .iter()
+        .find(|(c, _, _)| *c == code)
+        .map(|(_, s, d)| (*s, *d))
+}
+
+const EN_DATE: DateSymbols = DateSymbols {
+    months: [
+        "January",
+        "February",
+        "March",
+        "April",
+        "May",
+        "June",
+        "July",
+        "August",
+        "September",
+        "October",
+        "November",
+        "December",
+    ],
+    months_short: [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ],
+    weekdays: [
+        "Sunday",
+        "Monday",
+        "Tuesday",
+        "Wednesday",
+        "Thursday",
+        "Friday",
+        "Saturday",
+    ],
+    day_periods: ["AM", "PM"],
+    date: ["EEEE, MMMM d, y", "MMMM d, y", "MMM d, y", "M/d/yy"],
+    time: [
+        "h:mm:ss\u{202f}a z",
+        "h:mm:ss\u{202f}a z",
+        "h:mm:ss\u{202f}a",
+        "h:mm\u{202f}a",
+    ],
+    date_time: ["{1} at {0}", "{1} at {0}", "{1}, {0}", "{1}, {0}"],
+};
+
+const EN_RELATIVE: RelativeSymbols = RelativeSymbols {
+    units: [
+        [
+            "in {0} second",
+            "in {0} seconds",
+            "{0} second ago",
+            "{0} seconds ago",
+        ],
+        [
+            "in {0} minute",
+            "in {0} minutes",
+            "{0} minute ago",
+            "{0} minutes ago",
+        ],
+        [
+            "in {0} hour",
+            "in {0} hours",
+            "{0} hour ago",
+            "{0} hours ago",
+        ],
+        ["in {0} day", "in {0} days", "{0} day ago", "{0} days ago"],
+        [
+            "in {0} week",
+            "in {0} weeks",
+            "{0} week ago",
+            "{0} weeks ago",
+        ],
+        [
+            "in {0} month",
+            "in {0} months",
+            "{0} month ago",
+            "{0} months ago",
+        ],
+        [
+            "in {0} year",
+            "in {0} years",
+            "{0} year ago",
+            "{0} years ago",
+        ],
+    ],
+    named: [
+        ["yesterday", "today", "tomorrow"],
+        ["last week", "this week", "next week"],
+        ["last month", "this month", "next month"],
+        ["last year", "this year", "next year"],
+    ],
+    now: "now",
+};
+
+const PT_RELATIVE: RelativeSymbols = RelativeSymbols {
+    units: [
+        [
+            "em {0} segundo",
+            "em {0} segundos",
+            "há {0} segundo",
+            "há {0} segundos",
+        ],
+        [
+            "em {0} minuto",
+            "em {0} minutos",
+            "há {0} minuto",
+            "há {0} minutos",
+        ],
+        ["em {0} hora", "em {0} horas", "há {0} hora", "há {0} horas"],
+        ["em {0} dia", "em {0} dias", "há {0} dia", "há {0} dias"],
+        [
+            "em {0} semana",
+            "em {0} semanas",
+            "há {0} semana",
+            "há {0} semanas",
+        ],
+        ["em {0} mês", "em {0} meses", "há {0} mês", "há {0} meses"],
+        ["em {0} ano", "em {0} anos", "há {0} ano", "há {0} anos"],
+    ],
+    named: [
+        ["ontem", "hoje", "amanhã"],
+        ["semana passada", "esta semana", "próxima semana"],
+        ["mês passado", "este mês", "próximo mês"],
+        ["ano passado", "este ano", "próximo ano"],
+    ],
+    now: "agora",
+};
+
+const PT_PT_RELATIVE: RelativeSymbols = RelativeSymbols {
+    units: [
+        [
+            "dentro de {0} segundo",
+            "dentro de {0} segundos",
+            "há {0} segundo",
+            "há {0} segundos",
+        ],
+        [
+            "dentro de {0} minuto",
+            "dentro de {0} minutos",
+            "há {0} minuto",
+            "há {0} minutos",
+        ],
+        [
+            "dentro de {0} hora",
+            "dentro de {0} horas",
+            "há {0} hora",
+            "há {0} horas",
+        ],
+        [
+            "dentro de {0} dia",
+            "dentro de {0} dias",
+            "há {0} dia",
+            "há {0} dias",
+        ],
+        [
+            "dentro de {0} semana",
+            "dentro de {0} semanas",
+            "há {0} semana",
+            "há {0} semanas",
+        ],
+        [
+            "dentro de {0} mês",
+            "dentro de {0} meses",
+            "há {0} mês",
+            "há {0} meses",
+        ],
+        [
+            "dentro de {0} ano",
+            "dentro de {0} anos",
+            "há {0} ano",
+            "há {0} anos",
+        ],
+    ],
+    named: [
+        ["ontem", "hoje", "amanhã"],
+        ["semana passada", "esta semana", "próxima semana"],
+        ["mês passado", "este mês", "próximo mês"],
+        ["ano passado", "este ano", "próximo ano"],
+    ],
+    now: "agora",
+};
+
+const DE_RELATIVE: RelativeSymbols = RelativeSymbols {
+    units: [
+        [
+            "in {0} Sekunde",
+            "in {0} Sekunden",
+            "vor {0} Sekunde",
+            "vor {0} Sekunden",
+        ],
+        [
+            "in {0} Minute",
+            "in {0} Minuten",
+            "vor {0} Minute",
+            "vor {0} Minuten",
+        ],
+        [
+            "in {0} Stunde",
+            "in {0} Stunden",
+            "vor {0} Stunde",
+            "vor {0} Stunden",
+        ],
+        ["in {0} Tag", "in {0} Tagen", "vor {0} Tag", "vor {0} Tagen"],
+        [
+            "in {0} Woche",
+            "in {0} Wochen",
+            "vor {0} Woche",
+            "vor {0} Wochen",
+        ],
+        [
+            "in {0} Monat",
+            "in {0} Monaten",
+            "vor {0} Monat",
+            "vor {0} Monaten",
+        ],
+        [
+            "in {0} Jahr",
+            "in {0} Jahren",
+            "vor {0} Jahr",
+            "vor {0} Jahren",
+        ],
+    ],
+    named: [
+        ["gestern", "heute", "morgen"],
+        ["letzte Woche", "diese Woche", "nächste Woche"],
+        ["letzten Monat", "diesen Monat", "nächsten Monat"],
+        ["letztes Jahr", "dieses Jahr", "nächstes Jahr"],
+    ],
+    now: "jetzt",
+};
+
+const FR_RELATIVE: RelativeSymbols = RelativeSymbols {
+    units: [
+        [
+            "dans {0} seconde",
+            "dans {0} secondes",
+            "il y a {0} seconde",
+            "il y a {0} secondes",
+        ],
+        [
+            "dans {0} minute",
+            "dans {0} minutes",
+            "il y a {0} minute",
+            "il y a {0} minutes",
+        ],
+        [
+            "dans {0} heure",
+            "dans {0} heures",
+            "il y a {0} heure",
+            "il y a {0} heures",
+        ],
+        [
+            "dans {0} jour",
+            "dans {0} jours",
+            "il y a {0} jour",
+            "il y a {0} jours",
+        ],
+        [
+            "dans {0} semaine",
+            "dans {0} semaines",
+            "il y a {0} semaine",
+            "il y a {0} semaines",
+        ],
+        [
+            "dans {0} mois",
+            "dans {0} mois",
+            "il y a {0} mois",
+            "il y a {0} mois",
+        ],
+        [
+            "dans {0} an",
+            "dans {0} ans",
+            "il y a {0} an",
+            "il y a {0} ans",
+        ],
+    ],
+    named: [
+        ["hier", "aujourd’hui", "demain"],
+        [
+            "la semaine dernière",
+            "cette semaine",
+            "la semaine prochaine",
+        ],
+        ["le mois dernier", "ce mois-ci", "le mois prochain"],
+        ["l’année dernière", "cette année", "l’année prochaine"],
+    ],
+    now: "maintenant",
+};
+
+const IT_RELATIVE: RelativeSymbols = RelativeSymbols {
+    units: [
+        [
+            "tra {0} secondo",
+            "tra {0} secondi",
+            "{0} secondo fa",
+            "{0} secondi fa",
+        ],
+        [
+            "tra {0} minuto",
+            "tra {0} minuti",
+            "{0} minuto fa",
+            "{0} minuti fa",
+        ],
+        ["tra {0} ora", "tra {0} ore", "{0} ora fa", "{0} ore fa"],
+        [
+            "tra {0} giorno",
+            "tra {0} giorni",
+            "{0} giorno fa",
+            "{0} giorni fa",
+        ],
+        [
+            "tra {0} settimana",
+            "tra {0} settimane",
+            "{0} settimana fa",
+            "{0} settimane fa",
+        ],
+        ["tra {0} mese", "tra {0} mesi", "{0} mese fa", "{0} mesi fa"],
+        ["tra {0} anno", "tra {0} anni", "{0} anno fa", "{0} anni fa"],
+    ],
+    named: [
+        ["ieri", "oggi", "domani"],
+        ["settimana scorsa", "questa settimana", "settimana prossima"],
+        ["mese scorso", "questo mese", "mese prossimo"],
+        ["anno scorso", "quest’anno", "anno prossimo"],
+    ],
+    now: "ora",
+};
+
+const ROOT: LocaleData = LocaleData {
+    number: NumberSymbols {
+        decimal: ".",
+        group: ",",
+        minus: "-",
+        percent: "%",
+    },
+    currency: CurrencyFormat {
+        before: true,
+        spacing: "\u{a0}",
+        symbols: &[],
+        names: &[],
+    },
+    date: DateSymbols {
+        date: ["y-MM-dd"; 4],
+        time: ["HH:mm:ss z", "HH:mm:ss z", "HH:mm:ss", "HH:mm"],
+        date_time: ["{1} {0}"; 4],
+        ..EN_DATE
+    },
+    relative: EN_RELATIVE,
+};
+
+const EN: LocaleData = LocaleData {
+    currency: CurrencyFormat {
+        before: true,
+        spacing: "",
+        symbols: &[("JPY", "¥"), ("USD", "$")],
+        names: &[
+            ("BRL", "Brazilian real", "Brazilian reals"),
+            ("EUR", "euro", "euros"),
+            ("GBP", "British pound", "British pounds"),
+            ("JPY", "Japanese yen", "Japanese yen"),
+            ("USD", "US dollar", "US dollars"),
+        ],
+    },
+    date: EN_DATE,
+    ..ROOT
+};
+
+const EN_GB: LocaleData = LocaleData {
+    date: DateSymbols {
+        date: ["EEEE d MMMM y", "d MMMM y", "d MMM y", "dd/MM/y"],
+        time: ROOT.date.time,
+        ..EN_DATE
+    },
+    ..EN
+};
+
+const PT: LocaleData = LocaleData {
+    number: NumberSymbols {
+        decimal: ",",
+        group: ".",
+        ..ROOT.number
+    },
+    currency: CurrencyFormat {
+        names: &[
+            ("BRL", "real brasileiro", "reais brasileiros"),
+            ("EUR", "euro", "euros"),
+            ("GBP", "libra esterlina", "libras esterlinas"),
+            ("JPY", "iene japonês", "ienes japoneses"),
+            ("USD", "dólar americano", "dólares americanos"),
+        ],
+        ..ROOT.currency
+    },
+    date: DateSymbols {
+        months: [
+            "janeiro",
+            "fevereiro",
+            "março",
+            "abril",
+            "maio",
+            "junho",
+            "julho",
+            "agosto",
+            "setembro",
+            "outubro",
+            "novembro",
+            "dezembro",
+        ],
+        months_short: [
+            "jan.", "fev.", "mar.", "abr.", "mai.", "jun.", "jul.", "ago.", "set.", "out.", "nov.",
+            "dez.",
+        ],
+        weekdays: [
+            "domingo",
+            "segunda-feira",
+            "terça-feira",
+            "quarta-feira",
+            "quinta-feira",
+            "sexta-feira",
+            "sábado",
+        ],
+        date: [
+            "EEEE, d 'de' MMMM 'de' y",
+            "d 'de' MMMM 'de' y",
+            "d 'de' MMM 'de' y",
+            "dd/MM/y",
+        ],
+        date_time: ["{1} às {0}", "{1} às {0}", "{1}, {0}", "{1}, {0}"],
+        ..ROOT.date
+    },
+    relative: PT_RELATIVE,
+};
+
+const PT_PT: LocaleData = LocaleData {
+    number: NumberSymbols {
+        group: "\u{a0}",
+        ..PT.number
+    },
+    currency: CurrencyFormat {
+        before: false,
+        names: &[
+            ("BRL", "real brasileiro", "reais brasileiros"),
+            ("EUR", "euro", "euros"),
+            (
+                "GBP",
+                "libra esterlina britânica",
+                "libras esterlinas britânicas",
+            ),
+            ("JPY", "iene japonês", "ienes japoneses"),
+            (
+                "USD",
+                "dólar dos Estados Unidos",
+                "dólares dos Estados Unidos",
+            ),
+        ],
+        ..PT.currency
+    },
+    date: DateSymbols {
+        date: [
+            "EEEE, d 'de' MMMM 'de' y",
+            "d 'de' MMMM 'de' y",
+            "dd/MM/y",
+            "dd/MM/yy",
+        ],
+        ..PT.date
+    },
+    relative: PT_PT_RELATIVE,
+};
+
+const DE: LocaleData = LocaleData {
+    number: NumberSymbols {
+        percent: "\u{a0}%",
+        ..PT.number
+    },
+    currency: CurrencyFormat {
+        before: false,
+        names: &[],
+        ..ROOT.currency
+    },
+    date: DateSymbols {
+        months: [
+            "Januar",
+            "Februar",
+            "März",
+            "April",
+            "Mai",
+            "Juni",
+            "Juli",
+            "August",
+            "September",
+            "Oktober",
+            "November",
+            "Dezember",
+        ],
+        months_short: [
+            "Jan.", "Feb.", "März", "Apr.", "Mai", "Juni", "Juli", "Aug.", "Sept.", "Okt.", "Nov.",
+            "Dez.",
+        ],
+        weekdays: [
+            "Sonntag",
+            "Montag",
+            "Dienstag",
+            "Mittwoch",
+            "Donnerstag",
+            "Freitag",
+            "Samstag",
+        ],
+        date: ["EEEE, d. MMMM y", "d. MMMM y", "dd.MM.y", "dd.MM.yy"],
+        date_time: ["{1} um {0}", "{1} um {0}", "{1}, {0}", "{1}, {0}"],
+        ..ROOT.date
+    },
+    relative: DE_RELATIVE,
+};
+
+const FR: LocaleData = LocaleData {
+    number: NumberSymbols {
+        decimal: ",",
+        group: "\u{202f}",
+        minus: "-",
+        percent: "\u{a0}%",
+    },
+    currency: DE.currency,
+    date: DateSymbols {
+        months: [
+            "janvier",
+            "février",
+            "mars",
+            "avril",
+            "mai",
+            "juin",
+            "juillet",
+            "août",
+            "septembre",
+            "octobre",
+            "novembre",
+            "décembre",
+        ],
+        months_short: [
+            "janv.", "févr.", "mars", "avr.", "mai", "juin", "juil.", "août", "sept.", "oct.",
+            "nov.", "déc.",
+        ],
+        weekdays: [
+            "dimanche", "lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi",
+        ],
+        date: ["EEEE d MMMM y", "d MMMM y", "d MMM y", "dd/MM/y"],
+        date_time: ["{1} à {0}", "{1} à {0}", "{1}, {0}", "{1} {0}"],
+        ..ROOT.date
+    },
+    relative: FR_RELATIVE,
+};
+
+const IT: LocaleData = LocaleData {
+    number: PT.number,
+    currency: DE.currency,
+    date: DateSymbols {
+        months: [
+            "gennaio",
+            "febbraio",
+            "marzo",
+            "aprile",
+            "maggio",
+            "giugno",
+            "luglio",
+            "agosto",
+            "settembre",
+            "ottobre",
+            "novembre",
+            "dicembre",
+        ],
+        months_short: [
+            "gen", "feb", "mar", "apr", "mag", "giu", "lug", "ago", "set", "ott", "nov", "dic",
+        ],
+        weekdays: [
+            "domenica",
+            "lunedì",
+            "martedì",
+            "mercoledì",
+            "giovedì",
+            "venerdì",
+            "sabato",
+        ],
+        date: ["EEEE d MMMM y", "d MMMM y", "d MMM y", "dd/MM/yy"],
+        date_time: ["{1}, {0}"; 4],
+        ..ROOT.date
+    },
+    relative: IT_RELATIVE,
+};
+
+// sorted by tag, `language` or `language-REGION`
+const LOCALES: &[(&str, LocaleData)] = &[
+    ("de", DE),
+    ("en", EN),
+    ("en-GB", EN_GB),
+    ("en-UK", EN_GB),
+    ("fr", FR),
+    ("it", IT),
+    ("pt", PT),
+    ("pt-AO", PT_PT),
+    ("pt-MZ", PT_PT),
+    ("pt-PT", PT_PT),
+];
+
+fn find(tag: &str) -> Option<&'static LocaleData> {
+    LOCALES
+        .binary_search_by(|(t, _)| (*t).cmp(tag))
+        .ok()
+        .map(|i| &LOCALES[i].1)
+}
+
+/// Data for `lang`, matching `language-REGION`, then `language`, then the root locale.
+pub(crate) fn locale_data(lang: &LanguageIdentifier) -> &'static LocaleData {
+    let language = lang.language.as_str();
+    lang.region
+        .and_then(|region| find(&format!("{language}-{region}")))
+        .or_else(|| find(language))
+        .unwrap_or(&ROOT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locale_data_lookup() {
+        let lang = |s: &str| s.parse::<LanguageIdentifier>().unwrap();
+        assert!(LOCALES.windows(2).all(|w| w[0].0 < w[1].0), "sorted");
+        assert_eq!(locale_data(&lang("pt-PT")), &PT_PT);
+        assert_eq!(locale_data(&lang("pt-BR")), &PT);
+        assert_eq!(locale_data(&lang("pt")), &PT);
+        assert_eq!(locale_data(&lang("en-US")), &EN);
+        assert_eq!(locale_data(&lang("en-UK")), &EN_GB);
+        assert_eq!(locale_data(&lang("ja")), &ROOT);
+    }
+
+    #[test]
+    fn currency_data() {
+        assert_eq!(currency("JPY"), Some(("JP¥", 0)));
+        assert_eq!(currency("XXX"), None);
+        assert_eq!(EN.currency_symbol("USD"), Some("$"));
+        assert_eq!(PT.currency_symbol("USD"), Some("US$"));
+        assert_eq!(PT_PT.currency_symbol("EUR"), Some("€"));
+        assert_eq!(PT_PT.currency_name("EUR", true), Some("euro"));
+        assert_eq!(EN.currency_name("USD", false), Some("US dollars"));
+        assert_eq!(DE.currency_name("EUR", false), None);
+    }
+}
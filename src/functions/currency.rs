@@ -0,0 +1,116 @@
+//! `CURRENCY`, money amounts formatted by [`NumberFormat`](super::NumberFormat).
+
+use fluent_bundle::types::FluentNumberStyle;
+
+use super::{
+    cldr, named_str,
+    number::{number_arg, LocalizedNumber},
+};
+use crate::{types::Localized, FluentArgs, FluentValue};
+
+/// `CURRENCY`, a money amount in the bundle locale, accepts numbers and numeric strings
+///
+/// Options are `currency` (ISO 4217 code, required), `display` (`symbol`, `code` or
+/// `name`), `minorUnits` (`"true"` when the amount is an integer in minor units, like
+/// cents), `minimumFractionDigits` and `maximumFractionDigits`, the currency digits by
+/// default.
+///
+/// ```ftl
+/// total = { CURRENCY($cents, currency: "EUR", minorUnits: "true") }
+/// ```
+///
+/// With `$cents` as `500`, `en` renders `€5.00` and `pt-PT` renders `5,00 €`.
+///
+/// Returns a [`LocalizedNumber`], as `NUMBER`.
+pub fn currency<'a>(positional: &[FluentValue<'a>], named: &FluentArgs) -> FluentValue<'a> {
+    let Some(mut number) = number_arg(positional) else {
+        return FluentValue::Error;
+    };
+    // literal precision is replaced by the currency digits
+    number.options.minimum_fraction_digits = None;
+    number.options.merge(named);
+    number.options.style = FluentNumberStyle::Currency;
+    if let Some(display) = named_str(named, "display") {
+        number.options.currency_display = display.into();
+    }
+    let Some(code) = &number.options.currency else {
+        return FluentValue::Error;
+    };
+    if named_str(named, "minorUnits") == Some("true") {
+        if number.value.fract() != 0.0 {
+            return FluentValue::Error;
+        }
+        let digits = cldr::currency(code).map_or(2, |(_, d)| d);
+        number.value /= 10f64.powi(digits as i32);
+    }
+    Localized(LocalizedNumber(number)).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::f_args;
+
+    #[test]
+    fn currency_function() {
+        let args = f_args!["currency" => "EUR", "display" => "code"];
+        let value = currency(&["5.5".into()], &args);
+        let n = LocalizedNumber::from_value(&value).expect("Should be a localized number");
+        assert_eq!(n.value, 5.5);
+        assert_eq!(n.options.style, FluentNumberStyle::Currency);
+        assert_eq!(n.options.currency.as_deref(), Some("EUR"));
+        assert_eq!(n.options.minimum_fraction_digits, None);
+
+        let args = f_args!["currency" => "JPY", "minorUnits" => "true"];
+        let value = currency(&[500.into()], &args);
+        let n = LocalizedNumber::from_value(&value).expect("Should be a localized number");
+        assert_eq!(n.value, 500.0, "JPY has no minor units");
+        assert!(matches!(currency(&[5.5.into()], &args), FluentValue::Error));
+        assert!(matches!(
+            currency(&[5.into()], &FluentArgs::new()),
+            FluentValue::Error
+        ));
+    }
+
+    #[test]
+    fn localized_at_finish() {
+        use crate::{FluentMachine, LanguageIdentifier};
+
+        let ftl = r#"
+total-eur = { CURRENCY($cents, currency: "EUR", minorUnits: "true") }
+total-brl = { CURRENCY($cents, currency: "BRL", minorUnits: "true") }
+named = { CURRENCY($amount, currency: "EUR", display: "name") }
+"#;
+        let mut builder = FluentMachine::build();
+        for locale in ["en", "en-UK", "en-US", "pt", "pt-BR", "pt-PT"] {
+            builder = builder
+                .add_resource(locale, ftl)
+                .expect("Should add resource");
+        }
+        let i18n = builder
+            .set_fallback_locale("en")
+            .expect("Should set en")
+            .finish()
+            .expect("Should finish");
+
+        let t = |locale: &str, key: &str, args| {
+            let lang: LanguageIdentifier = locale.parse().unwrap();
+            i18n.t(&[&lang], key.try_into().unwrap(), Some(&args))
+        };
+        let eur = || f_args!["cents" => 500];
+        let brl = || f_args!["cents" => -123456];
+        for en in ["en", "en-UK", "en-US"] {
+            assert_eq!(t(en, "total-eur", eur()), "€5.00");
+            assert_eq!(t(en, "total-brl", brl()), "-R$1,234.56");
+        }
+        assert_eq!(t("pt-PT", "total-eur", eur()), "5,00\u{a0}€");
+        assert_eq!(t("pt-PT", "total-brl", brl()), "-1\u{a0}234,56\u{a0}R$");
+        for pt in ["pt", "pt-BR"] {
+            assert_eq!(t(pt, "total-eur", eur()), "€\u{a0}5,00");
+            assert_eq!(t(pt, "total-brl", brl()), "-R$\u{a0}1.234,56");
+        }
+        assert_eq!(t("en", "named", f_args!["amount" => 1]), "1.00 euros");
+        assert_eq!(t("pt", "named", f_args!["amount" => 1]), "1,00 euro");
+        assert_eq!(t("pt-PT", "named", f_args!["amount" => 1]), "1,00 euros");
+    }
+}
@@ -0,0 +1,487 @@
+//! `DATETIME` and [`FluentDateTime`], dates formatted with the bundle locale.
+
+use std::{
+    borrow::Cow,
+    convert::Infallible,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use fluent_bundle::types::FluentType;
+use intl_memoizer::Memoizable;
+
+use super::cldr::{locale_data, DateSymbols};
+use crate::{FluentArgs, FluentValue, LanguageIdentifier};
+
+/// Date and time styles, as in `dateStyle` and `timeStyle`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DateTimeStyle {
+    Full,
+    Long,
+    Medium,
+    Short,
+}
+
+impl DateTimeStyle {
+    fn parse(style: &str) -> Option<Self> {
+        match style {
+            "full" => Some(Self::Full),
+            "long" => Some(Self::Long),
+            "medium" => Some(Self::Medium),
+            "short" => Some(Self::Short),
+            _ => None,
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Formatting options of a [`FluentDateTime`].
+///
+/// Without styles the date is formatted in the `medium` style.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct DateTimeOptions {
+    pub date_style: Option<DateTimeStyle>,
+    pub time_style: Option<DateTimeStyle>,
+    /// offset from UTC in minutes, clamped to less than a day when formatting
+    pub utc_offset: i32,
+}
+
+impl DateTimeOptions {
+    /// Merges `dateStyle`, `timeStyle` and `utcOffset` from the function named arguments.
+    pub fn merge(&mut self, named: &FluentArgs) {
+        for (key, value) in named.iter() {
+            match (key, value) {
+                ("dateStyle", FluentValue::String(s)) => {
+                    self.date_style = DateTimeStyle::parse(s).or(self.date_style);
+                }
+                ("timeStyle", FluentValue::String(s)) => {
+                    self.time_style = DateTimeStyle::parse(s).or(self.time_style);
+                }
+                ("utcOffset", FluentValue::Number(n)) => {
+                    self.utc_offset = n.value as i32;
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// A point in time, passed as an argument and formatted in the bundle locale.
+///
+/// Converts from [`SystemTime`] or Unix timestamps, in seconds.
+///
+/// # Example
+///
+/// ```
+/// use std::time::SystemTime;
+/// use fi18n::{f_args, functions::FluentDateTime};
+///
+/// let args = f_args![
+///     "now" => FluentDateTime::from(SystemTime::now()),
+///     "release" => FluentDateTime::from_unix(1792165507),
+/// ];
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct FluentDateTime {
+    seconds: i64,
+    options: DateTimeOptions,
+}
+
+impl FluentDateTime {
+    /// Date time from a Unix timestamp in seconds.
+    pub fn from_unix(seconds: i64) -> Self {
+        Self {
+            seconds,
+            options: DateTimeOptions::default(),
+        }
+    }
+
+    /// Sets the formatting options, overridden by `DATETIME` named arguments.
+    pub fn with_options(mut self, options: DateTimeOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Unix timestamp in seconds.
+    pub fn seconds(&self) -> i64 {
+        self.seconds
+    }
+
+    pub fn options(&self) -> &DateTimeOptions {
+        &self.options
+    }
+}
+
+impl From<SystemTime> for FluentDateTime {
+    fn from(time: SystemTime) -> Self {
+        let seconds = match time.duration_since(UNIX_EPOCH) {
+            Ok(d) => d.as_secs() as i64,
+            Err(e) => -(e.duration().as_secs_f64().ceil() as i64),
+        };
+        Self::from_unix(seconds)
+    }
+}
+
+impl<'a> From<FluentDateTime> for FluentValue<'a> {
+    fn from(value: FluentDateTime) -> Self {
+        FluentValue::Custom(Box::new(value))
+    }
+}
+
+impl FluentType for FluentDateTime {
+    fn duplicate(&self) -> Box<dyn FluentType + Send> {
+        Box::new(self.clone())
+    }
+
+    fn as_string(&self, intls: &intl_memoizer::IntlLangMemoizer) -> Cow<'static, str> {
+        intls
+            .with_try_get::<DateTimeFormat, _, _>(self.options.clone(), |f| f.format(self.seconds))
+            .unwrap_or_else(|e| match e {})
+            .into()
+    }
+
+    fn as_string_threadsafe(
+        &self,
+        intls: &intl_memoizer::concurrent::IntlLangMemoizer,
+    ) -> Cow<'static, str> {
+        intls
+            .with_try_get::<DateTimeFormat, _, _>(self.options.clone(), |f| f.format(self.seconds))
+            .unwrap_or_else(|e| match e {})
+            .into()
+    }
+}
+
+/// Date time formatter for a locale and a set of options, memoized per bundle.
+///
+/// Uses the CLDR month and weekday names and field order of the locale.
+#[derive(Debug, Clone)]
+pub struct DateTimeFormat {
+    symbols: &'static DateSymbols,
+    options: DateTimeOptions,
+}
+
+impl Memoizable for DateTimeFormat {
+    type Args = DateTimeOptions;
+    type Error = Infallible;
+
+    fn construct(lang: LanguageIdentifier, options: Self::Args) -> Result<Self, Self::Error> {
+        Ok(Self {
+            symbols: &locale_data(&lang).date,
+            options,
+        })
+    }
+}
+
+// calendar fields of a timestamp
+struct Fields {
+    year: i64,
+    month: usize,
+    day: i64,
+    weekday: usize,
+    hour: i64,
+    minute: i64,
+    second: i64,
+}
+
+impl Fields {
+    fn new(seconds: i64) -> Self {
+        let days = seconds.div_euclid(86_400);
+        let time = seconds.rem_euclid(86_400);
+        let (year, month, day) = civil_from_days(days);
+        Self {
+            year,
+            month,
+            day,
+            // 1970-01-01 was a thursday
+            weekday: (days + 4).rem_euclid(7) as usize,
+            hour: time / 3600,
+            minute: time % 3600 / 60,
+            second: time % 60,
+        }
+    }
+}
+
+// proleptic gregorian date of days since 1970-01-01, by Howard Hinnant
+fn civil_from_days(days: i64) -> (i64, usize, i64) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month as usize, day)
+}
+
+// supported timestamps, from 0001-01-01T00:00:00Z to 9999-12-31T23:59:59Z
+const MIN_SECONDS: i64 = -62_135_596_800;
+const MAX_SECONDS: i64 = 253_402_300_799;
+// offsets up to a day, in minutes
+const MAX_OFFSET: i32 = 24 * 60 - 1;
+
+impl DateTimeFormat {
+    /// Formats the Unix timestamp `seconds`, the local time is clamped to the years 1 to 9999.
+    pub fn format(&self, seconds: i64) -> String {
+        let o = &self.options;
+        let offset = i64::from(self.utc_offset()) * 60;
+        let fields = Fields::new(
+            seconds
+                .saturating_add(offset)
+                .clamp(MIN_SECONDS, MAX_SECONDS),
+        );
+        let date_style = match (o.date_style, o.time_style) {
+            (None, None) => Some(DateTimeStyle::Medium),
+            (style, _) => style,
+        };
+        let date = date_style.map(|s| self.pattern(self.symbols.date[s.index()], &fields));
+        let time = o
+            .time_style
+            .map(|s| self.pattern(self.symbols.time[s.index()], &fields));
+        match (date, time) {
+            (Some(date), Some(time)) => {
+                let join = self.symbols.date_time[date_style.unwrap().index()];
+                join.replace("{0}", &time).replace("{1}", &date)
+            }
+            (date, time) => date.or(time).unwrap_or_default(),
+        }
+    }
+
+    // interprets the CLDR pattern letters, text in quotes is literal
+    fn pattern(&self, pattern: &str, f: &Fields) -> String {
+        let s = self.symbols;
+        let mut out = String::with_capacity(pattern.len() * 2);
+        let mut chars = pattern.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c == '\'' {
+                if chars.peek() == Some(&'\'') {
+                    chars.next();
+                    out.push('\'');
+                    continue;
+                }
+                for c in chars.by_ref() {
+                    if c == '\'' {
+                        break;
+                    }
+                    out.push(c);
+                }
+                continue;
+            }
+            if !c.is_ascii_alphabetic() {
+                out.push(c);
+                continue;
+            }
+            let mut n = 1;
+            while chars.peek() == Some(&c) {
+                chars.next();
+                n += 1;
+            }
+            match (c, n) {
+                ('y', 2) => out.push_str(&format!("{:02}", f.year.rem_euclid(100))),
+                ('y', _) => out.push_str(&format!("{:0n$}", f.year)),
+                ('M', 1 | 2) => out.push_str(&format!("{:0n$}", f.month)),
+                ('M', 3) => out.push_str(s.months_short[f.month - 1]),
+                ('M', _) => out.push_str(s.months[f.month - 1]),
+                ('d', _) => out.push_str(&format!("{:0n$}", f.day)),
+                ('E', _) => out.push_str(s.weekdays[f.weekday]),
+                ('h', _) => out.push_str(&format!("{:0n$}", (f.hour + 11) % 12 + 1)),
+                ('H', _) => out.push_str(&format!("{:0n$}", f.hour)),
+                ('m', _) => out.push_str(&format!("{:0n$}", f.minute)),
+                ('s', _) => out.push_str(&format!("{:0n$}", f.second)),
+                ('a', _) => out.push_str(s.day_periods[usize::from(f.hour >= 12)]),
+                ('z', _) => out.push_str(&self.zone()),
+                _ => (0..n).for_each(|_| out.push(c)),
+            }
+        }
+        out
+    }
+
+    fn utc_offset(&self) -> i32 {
+        self.options.utc_offset.clamp(-MAX_OFFSET, MAX_OFFSET)
+    }
+
+    fn zone(&self) -> String {
+        let offset = self.utc_offset();
+        let sign = if offset < 0 { '-' } else { '+' };
+        match (offset.abs() / 60, offset.abs() % 60) {
+            (0, 0) => "UTC".to_string(),
+            (h, 0) => format!("GMT{sign}{h}"),
+            (h, m) => format!("GMT{sign}{h}:{m:02}"),
+        }
+    }
+}
+
+/// `DATETIME`, a date time in the bundle locale, accepts a [`FluentDateTime`] or a Unix
+/// timestamp in seconds
+///
+/// Options are `dateStyle` and `timeStyle` (`full`, `long`, `medium` or `short`), and
+/// `utcOffset` in minutes, times are in UTC by default.
+pub fn datetime<'a>(positional: &[FluentValue<'a>], named: &FluentArgs) -> FluentValue<'a> {
+    let mut value = match positional {
+        [FluentValue::Custom(c)] => match c.as_ref().as_any().downcast_ref::<FluentDateTime>() {
+            Some(value) => value.clone(),
+            None => return FluentValue::Error,
+        },
+        [FluentValue::Number(n)] if n.value.is_finite() => {
+            FluentDateTime::from_unix(n.value.floor() as i64)
+        }
+        _ => return FluentValue::Error,
+    };
+    value.options.merge(named);
+    value.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::f_args;
+
+    // 2026-10-16 15:45:07 UTC, a friday
+    const TS: i64 = 1_792_165_507;
+
+    fn format(lang: &str, date: Option<&str>, time: Option<&str>) -> String {
+        let options = DateTimeOptions {
+            date_style: date.and_then(DateTimeStyle::parse),
+            time_style: time.and_then(DateTimeStyle::parse),
+            utc_offset: 0,
+        };
+        DateTimeFormat::construct(lang.parse().unwrap(), options)
+            .unwrap()
+            .format(TS)
+    }
+
+    #[test]
+    fn calendar_fields() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(-1), (1969, 12, 31));
+        assert_eq!(civil_from_days(11_016), (2000, 2, 29));
+        let f = Fields::new(TS);
+        assert_eq!((f.year, f.month, f.day, f.weekday), (2026, 10, 16, 5));
+        assert_eq!((f.hour, f.minute, f.second), (15, 45, 7));
+        let f = Fields::new(-3600);
+        assert_eq!((f.year, f.month, f.day, f.hour), (1969, 12, 31, 23));
+    }
+
+    #[test]
+    fn date_styles() {
+        assert_eq!(format("en", Some("long"), None), "October 16, 2026");
+        assert_eq!(format("pt-PT", Some("long"), None), "16 de outubro de 2026");
+        assert_eq!(
+            format("pt", Some("full"), None),
+            "sexta-feira, 16 de outubro de 2026"
+        );
+        assert_eq!(format("en", None, None), "Oct 16, 2026");
+        assert_eq!(format("pt", Some("medium"), None), "16 de out. de 2026");
+        assert_eq!(format("pt-PT", Some("medium"), None), "16/10/2026");
+        assert_eq!(format("en", Some("short"), None), "10/16/26");
+        assert_eq!(format("en-UK", Some("short"), None), "16/10/2026");
+        assert_eq!(format("de", Some("long"), None), "16. Oktober 2026");
+        assert_eq!(format("ja", Some("long"), None), "2026-10-16");
+    }
+
+    #[test]
+    fn time_styles() {
+        assert_eq!(format("en", None, Some("short")), "3:45\u{202f}PM");
+        assert_eq!(format("pt", None, Some("medium")), "15:45:07");
+        assert_eq!(
+            format("en", Some("long"), Some("short")),
+            "October 16, 2026 at 3:45\u{202f}PM"
+        );
+        assert_eq!(
+            format("pt-PT", Some("long"), Some("short")),
+            "16 de outubro de 2026 às 15:45"
+        );
+        assert_eq!(format("en-GB", None, Some("long")), "15:45:07 UTC");
+
+        let options = DateTimeOptions {
+            time_style: Some(DateTimeStyle::Long),
+            utc_offset: -150,
+            ..Default::default()
+        };
+        let f = DateTimeFormat::construct("pt".parse().unwrap(), options).unwrap();
+        assert_eq!(f.format(TS), "13:15:07 GMT-2:30");
+    }
+
+    #[test]
+    fn out_of_range_values_are_clamped() {
+        let format = |seconds, utc_offset| {
+            let options = DateTimeOptions {
+                date_style: Some(DateTimeStyle::Short),
+                time_style: Some(DateTimeStyle::Long),
+                utc_offset,
+            };
+            DateTimeFormat::construct("pt".parse().unwrap(), options)
+                .unwrap()
+                .format(seconds)
+        };
+        assert_eq!(format(i64::MAX, 60), "31/12/9999, 23:59:59 GMT+1");
+        assert_eq!(format(i64::MIN, -60), "01/01/1, 00:00:00 GMT-1");
+        assert_eq!(format(0, i32::MIN), "31/12/1969, 00:01:00 GMT-23:59");
+        assert_eq!(format(0, i32::MAX), "01/01/1970, 23:59:00 GMT+23:59");
+
+        let args = f_args!["utcOffset" => 60];
+        let value = datetime(&[1e300.into()], &args);
+        let intls = intl_memoizer::concurrent::IntlLangMemoizer::new("en".parse().unwrap());
+        let FluentValue::Custom(c) = value else {
+            panic!("Should be a date time");
+        };
+        assert_eq!(c.as_string_threadsafe(&intls), "Dec 31, 9999");
+    }
+
+    #[test]
+    fn datetime_function() {
+        let args = f_args!["dateStyle" => "long", "timeStyle" => "bogus"];
+        let FluentValue::Custom(c) = datetime(&[(TS as f64).into()], &args) else {
+            panic!("Should be a date time");
+        };
+        let value = c
+            .as_ref()
+            .as_any()
+            .downcast_ref::<FluentDateTime>()
+            .unwrap();
+        assert_eq!(value.seconds(), TS);
+        assert_eq!(value.options().date_style, Some(DateTimeStyle::Long));
+        assert_eq!(value.options().time_style, None);
+
+        let now = FluentDateTime::from(UNIX_EPOCH + std::time::Duration::from_secs(60));
+        assert_eq!(now.seconds(), 60);
+        let before = FluentDateTime::from(UNIX_EPOCH - std::time::Duration::from_millis(1500));
+        assert_eq!(before.seconds(), -2);
+        assert!(matches!(
+            datetime(&["today".into()], &args),
+            FluentValue::Error
+        ));
+    }
+
+    #[test]
+    fn localized_at_finish() {
+        use crate::{FluentMachine, LanguageIdentifier};
+
+        let ftl = r#"
+released = { DATETIME($ts, dateStyle: "long") }
+plain = { $ts }
+"#;
+        let i18n = FluentMachine::build()
+            .add_resource("en", ftl)
+            .expect("Should add en")
+            .add_resource("pt-PT", ftl)
+            .expect("Should add pt-PT")
+            .set_fallback_locale("en")
+            .expect("Should set en")
+            .finish()
+            .expect("Should finish");
+        let en: LanguageIdentifier = "en".parse().unwrap();
+        let pt: LanguageIdentifier = "pt-PT".parse().unwrap();
+        let t = |lang, key: &str, args| i18n.t(&[lang], key.try_into().unwrap(), Some(&args));
+
+        let args = || f_args!["ts" => FluentDateTime::from_unix(TS)];
+        assert_eq!(t(&en, "released", args()), "October 16, 2026");
+        assert_eq!(t(&pt, "released", args()), "16 de outubro de 2026");
+        assert_eq!(t(&pt, "plain", args()), "16/10/2026");
+        let args = f_args!["ts" => TS];
+        assert_eq!(t(&en, "released", args), "October 16, 2026");
+    }
+}
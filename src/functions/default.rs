@@ -0,0 +1,235 @@
+//! `DEFAULT`, a fallback for missing or empty values.
+
+use fluent_syntax::ast::{Expression, InlineExpression, Pattern, PatternElement};
+
+use crate::{FluentArgs, FluentValue, MachineBundle};
+
+/// Message references followed while looking for `DEFAULT` variables
+const MAX_DEPTH: u8 = 8;
+
+/// `DEFAULT`, returns the second argument when the first is missing or empty
+pub fn default<'a>(positional: &[FluentValue<'a>], _named: &FluentArgs) -> FluentValue<'a> {
+    match positional {
+        [FluentValue::Error | FluentValue::None, fallback] => fallback.clone(),
+        [FluentValue::String(s), fallback] if s.is_empty() => fallback.clone(),
+        [value, _] => value.clone(),
+        _ => FluentValue::Error,
+    }
+}
+
+/// Returns `args` with the missing variables only used as the first `DEFAULT` argument set to
+/// [`FluentValue::None`], so the resolver doesn't report them and `DEFAULT` gets its fallback.
+///
+/// Looks into `pattern`, its select variants and the messages it references, returns `None`
+/// when no variable is missing.
+pub(crate) fn fill_defaulted<'a>(
+    bundle: &'a MachineBundle,
+    pattern: &'a Pattern<&'a str>,
+    args: Option<&'a FluentArgs<'a>>,
+) -> Option<FluentArgs<'a>> {
+    let mut missing: Vec<&str> = vec![];
+    Variables::new(bundle, &mut |name, defaulted| {
+        if defaulted && args.and_then(|a| a.get(name)).is_none() && !missing.contains(&name) {
+            missing.push(name);
+        }
+    })
+    .pattern(pattern);
+    if missing.is_empty() {
+        return None;
+    }
+    Variables::new(bundle, &mut |name, defaulted| {
+        if !defaulted {
+            missing.retain(|m| *m != name);
+        }
+    })
+    .pattern(pattern);
+    if missing.is_empty() {
+        return None;
+    }
+
+    let mut filled =
+        FluentArgs::with_capacity(args.map_or(0, |a| a.iter().count()) + missing.len());
+    for (key, value) in args.into_iter().flat_map(FluentArgs::iter) {
+        filled.set(key, value.clone());
+    }
+    for name in missing {
+        filled.set(name, FluentValue::None);
+    }
+    Some(filled)
+}
+
+/// Visits the variables of a pattern, telling whether each is the first `DEFAULT` argument
+struct Variables<'a, 'v> {
+    bundle: &'a MachineBundle,
+    visit: &'v mut dyn FnMut(&'a str, bool),
+    depth: u8,
+}
+
+impl<'a, 'v> Variables<'a, 'v> {
+    fn new(bundle: &'a MachineBundle, visit: &'v mut dyn FnMut(&'a str, bool)) -> Self {
+        Self {
+            bundle,
+            visit,
+            depth: 0,
+        }
+    }
+
+    fn pattern(&mut self, pattern: &'a Pattern<&'a str>) {
+        for element in pattern.elements.iter() {
+            if let PatternElement::Placeable { expression } = element {
+                self.expression(expression);
+            }
+        }
+    }
+
+    fn expression(&mut self, expression: &'a Expression<&'a str>) {
+        match expression {
+            Expression::Select { selector, variants } => {
+                self.inline(selector);
+                for variant in variants {
+                    self.pattern(&variant.value);
+                }
+            }
+            Expression::Inline(inline) => self.inline(inline),
+        }
+    }
+
+    fn inline(&mut self, inline: &'a InlineExpression<&'a str>) {
+        match inline {
+            InlineExpression::VariableReference { id } => (self.visit)(id.name, false),
+            InlineExpression::FunctionReference { id, arguments } => {
+                let mut positional = arguments.positional.iter();
+                if id.name == "DEFAULT" {
+                    if let Some(InlineExpression::VariableReference { id }) =
+                        arguments.positional.first()
+                    {
+                        (self.visit)(id.name, true);
+                        positional.next();
+                    }
+                }
+                for inline in positional.chain(arguments.named.iter().map(|n| &n.value)) {
+                    self.inline(inline);
+                }
+            }
+            // terms only see their own arguments, the arguments see ours
+            InlineExpression::TermReference {
+                arguments: Some(arguments),
+                ..
+            } => {
+                for inline in arguments
+                    .positional
+                    .iter()
+                    .chain(arguments.named.iter().map(|n| &n.value))
+                {
+                    self.inline(inline);
+                }
+            }
+            // referenced messages are formatted with the same arguments
+            InlineExpression::MessageReference { id, attribute } => {
+                let Some(message) = self.bundle.get_message(id.name) else {
+                    return;
+                };
+                let pattern = match attribute {
+                    Some(attribute) => message.get_attribute(attribute.name).map(|a| a.value()),
+                    None => message.value(),
+                };
+                if let Some(pattern) = pattern.filter(|_| self.depth < MAX_DEPTH) {
+                    self.depth += 1;
+                    self.pattern(pattern);
+                    self.depth -= 1;
+                }
+            }
+            InlineExpression::Placeable { expression } => self.expression(expression),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{f_args, FluentMachine, LanguageIdentifier};
+
+    #[test]
+    fn default_function() {
+        let none = FluentArgs::new();
+        assert_eq!(
+            default(&[FluentValue::Error, "guest".into()], &none),
+            "guest".into()
+        );
+        assert_eq!(default(&["".into(), "guest".into()], &none), "guest".into());
+        assert_eq!(
+            default(&["Joe".into(), "guest".into()], &none),
+            "Joe".into()
+        );
+    }
+
+    #[test]
+    fn missing_default_variable_is_not_an_error() {
+        let i18n = FluentMachine::build()
+            .add_resource(
+                "en",
+                r#"
+greet = Hello { DEFAULT($name, "guest") }
+repeat = Hello { DEFAULT($name, "guest") }, { $name }
+unread = { $count ->
+    [one] One message for { DEFAULT($name, "guest") }
+   *[other] Messages for { DEFAULT($name, "guest") }
+}
+login = Login
+    .title = Hello { DEFAULT($name, "guest") }
+welcome = { greet }, { login.title }
+"#,
+            )
+            .expect("Should add en")
+            .set_strict(true)
+            .finish()
+            .expect("Should finish strict");
+        let en: LanguageIdentifier = "en".parse().unwrap();
+
+        assert_eq!(
+            i18n.try_t(&[&en], "greet".try_into().unwrap(), None)
+                .expect("Should not report $name"),
+            "Hello \u{2068}guest\u{2069}"
+        );
+        assert_eq!(
+            i18n.try_t(
+                &[&en],
+                "greet".try_into().unwrap(),
+                Some(&f_args!["name" => "Joe"])
+            )
+            .unwrap(),
+            "Hello \u{2068}Joe\u{2069}"
+        );
+        assert!(i18n
+            .t_with_errors(&[&en], "greet".try_into().unwrap(), None)
+            .errors
+            .is_empty());
+        assert_eq!(
+            i18n.t_with_errors(&[&en], "repeat".try_into().unwrap(), None)
+                .errors
+                .len(),
+            2,
+            "$name is used outside DEFAULT, both errors are kept"
+        );
+        assert_eq!(
+            i18n.try_t(
+                &[&en],
+                "unread".try_into().unwrap(),
+                Some(&f_args!["count" => 1])
+            )
+            .expect("Should not report $name in variants"),
+            "One message for \u{2068}guest\u{2069}"
+        );
+        assert_eq!(
+            i18n.try_t(&[&en], "login.title".try_into().unwrap(), None)
+                .expect("Should not report $name in attributes"),
+            "Hello \u{2068}guest\u{2069}"
+        );
+        assert_eq!(
+            i18n.try_t(&[&en], "welcome".try_into().unwrap(), None)
+                .expect("Should not report $name in references"),
+            "Hello \u{2068}guest\u{2069}, Hello \u{2068}guest\u{2069}"
+        );
+    }
+}
@@ -0,0 +1,434 @@
+/*!
+Builtin functions, each enabled by its own cargo feature, or all by `functions`, only
+`TITLE` is enabled by default.
+
+Enabled functions are added to every bundle at
+[`FluentMachineBuilder::finish`](crate::builders::FluentMachineBuilder::finish), which returns
+[`Error::Overriding`] if a function with the same name was added by
+[`FluentMachineBuilder::add_function`](crate::builders::FluentMachineBuilder::add_function).
+
+| Function | Feature | Example | Output |
+|---|---|---|---|
+| `TITLE` | `with-title` | `TITLE("john doe")` | `John doe` |
+| `UPPER` | `fn-upper` | `UPPER("Hello")` | `HELLO` |
+| `LOWER` | `fn-lower` | `LOWER("Hello")` | `hello` |
+| `CAPITALIZE` | `fn-capitalize` | `CAPITALIZE("hELLO")` | `Hello` |
+| `TRUNCATE` | `fn-truncate` | `TRUNCATE("Hello", length: 2)` | `He…` |
+| `TRIM` | `fn-trim` | `TRIM("  Hello ")` | `Hello` |
+| `PAD` | `fn-pad` | `PAD(42, width: 5, fill: "0", align: "end")` | `00042` |
+| `DEFAULT` | `fn-default` | `DEFAULT($name, "guest")` | `guest` |
+| `LEN` | `fn-len` | `LEN("Olá")` | `3` |
+| `NUMBER` | `fn-number` | `NUMBER($n, minimumFractionDigits: 2)` | `5,000.50` |
+| `CURRENCY` | `fn-currency` | `CURRENCY($n, currency: "EUR")` | `€5.00` |
+| `DATETIME` | `fn-datetime` | `DATETIME($ts, dateStyle: "long")` | `October 16, 2026` |
+| `RELATIVE_TIME` | `fn-relative-time` | `RELATIVE_TIME(-259200)` | `3 days ago` |
+
+Strings and numbers are accepted as text, any other value returns an error.
+
+Numbers returned by `NUMBER` and `CURRENCY` are formatted with the decimal and grouping
+symbols of the bundle locale, `NUMBER(5000.5)` renders as `5,000.5` in `en` and `5 000,5` in
+`pt-PT`, while a plain `{ $n }` keeps the fluent formatting, `5000.5`. See [`NumberFormat`]
+for the supported options and [`LocalizedNumber`] for the returned value.
+
+Dates are passed as [`FluentDateTime`], from a [`SystemTime`](std::time::SystemTime) or a
+Unix timestamp, and are formatted with the month names and field order of the bundle locale.
+Relative times take a number of seconds from now, or a [`FluentRelativeTime`], the unit
+and plural form are chosen per locale.
+
+`DEFAULT` replaces missing variables, the unknown variable error is not reported for variables
+only used as its first argument.
+*/
+
+#[cfg(any(feature = "fn-number", feature = "fn-datetime"))]
+mod cldr;
+#[cfg(feature = "fn-currency")]
+mod currency;
+#[cfg(feature = "fn-datetime")]
+mod datetime;
+#[cfg(feature = "fn-default")]
+mod default;
+#[cfg(feature = "fn-number")]
+mod number;
+#[cfg(feature = "fn-relative-time")]
+mod relative_time;
+
+#[cfg(feature = "fn-currency")]
+pub use currency::currency;
+#[cfg(feature = "fn-datetime")]
+pub use datetime::{datetime, DateTimeFormat, DateTimeOptions, DateTimeStyle, FluentDateTime};
+#[cfg(feature = "fn-default")]
+pub use default::default;
+#[cfg(feature = "fn-default")]
+pub(crate) use default::fill_defaulted;
+#[cfg(feature = "fn-number")]
+pub use number::{number, LocalizedNumber, NumberFormat};
+#[cfg(feature = "fn-relative-time")]
+pub use relative_time::{
+    relative_time, FluentRelativeTime, RelativeTimeFormat, RelativeTimeOptions, RelativeTimeUnit,
+};
+
+#[cfg(any(
+    feature = "fn-upper",
+    feature = "fn-lower",
+    feature = "fn-capitalize",
+    feature = "fn-truncate",
+    feature = "fn-trim",
+    feature = "fn-pad",
+    feature = "fn-len"
+))]
+use std::borrow::Cow;
+
+use crate::{Error, FluentError, MachineBundle};
+#[cfg(any(
+    feature = "with-title",
+    feature = "fn-upper",
+    feature = "fn-lower",
+    feature = "fn-capitalize",
+    feature = "fn-truncate",
+    feature = "fn-trim",
+    feature = "fn-pad",
+    feature = "fn-len",
+    feature = "fn-currency"
+))]
+use crate::{FluentArgs, FluentValue};
+
+// value as text, numbers use the default number format
+#[cfg(any(
+    feature = "fn-upper",
+    feature = "fn-lower",
+    feature = "fn-capitalize",
+    feature = "fn-truncate",
+    feature = "fn-trim",
+    feature = "fn-pad",
+    feature = "fn-len"
+))]
+fn text<'a>(value: &'a FluentValue) -> Option<Cow<'a, str>> {
+    match value {
+        FluentValue::String(s) => Some(Cow::Borrowed(s)),
+        FluentValue::Number(n) => Some(n.as_string()),
+        #[cfg(feature = "fn-number")]
+        value => LocalizedNumber::from_value(value).map(|n| n.as_string()),
+        #[cfg(not(feature = "fn-number"))]
+        _ => None,
+    }
+}
+
+#[cfg(any(feature = "fn-truncate", feature = "fn-pad", feature = "fn-currency"))]
+fn named<'a>(named: &'a FluentArgs, key: &str) -> Option<&'a FluentValue<'a>> {
+    named.iter().find(|(k, _)| *k == key).map(|(_, v)| v)
+}
+
+#[cfg(any(feature = "fn-truncate", feature = "fn-pad"))]
+fn named_number(named: &FluentArgs, key: &str) -> Option<usize> {
+    match self::named(named, key) {
+        Some(FluentValue::Number(n)) if n.value >= 0.0 => Some(n.value as usize),
+        _ => None,
+    }
+}
+
+#[cfg(any(feature = "fn-truncate", feature = "fn-pad", feature = "fn-currency"))]
+fn named_str<'a>(named: &'a FluentArgs, key: &str) -> Option<&'a str> {
+    match self::named(named, key) {
+        Some(FluentValue::String(s)) => Some(s),
+        _ => None,
+    }
+}
+
+/// `TITLE`, uppercases the first character
+#[cfg(feature = "with-title")]
+pub fn title<'a>(positional: &[FluentValue<'a>], _named: &FluentArgs) -> FluentValue<'a> {
+    match positional {
+        [FluentValue::String(s)] => {
+            let mut c = s.chars();
+            match c.next() {
+                None => String::new(),
+                Some(f) => f.to_uppercase().collect::<String>() + c.as_str(),
+            }
+            .into()
+        }
+        _ => FluentValue::Error,
+    }
+}
+
+/// `UPPER`, uppercases all characters
+#[cfg(feature = "fn-upper")]
+pub fn upper<'a>(positional: &[FluentValue<'a>], _named: &FluentArgs) -> FluentValue<'a> {
+    match positional {
+        [value] => text(value).map_or(FluentValue::Error, |s| s.to_uppercase().into()),
+        _ => FluentValue::Error,
+    }
+}
+
+/// `LOWER`, lowercases all characters
+#[cfg(feature = "fn-lower")]
+pub fn lower<'a>(positional: &[FluentValue<'a>], _named: &FluentArgs) -> FluentValue<'a> {
+    match positional {
+        [value] => text(value).map_or(FluentValue::Error, |s| s.to_lowercase().into()),
+        _ => FluentValue::Error,
+    }
+}
+
+/// `CAPITALIZE`, uppercases the first character and lowercases the remaining
+#[cfg(feature = "fn-capitalize")]
+pub fn capitalize<'a>(positional: &[FluentValue<'a>], _named: &FluentArgs) -> FluentValue<'a> {
+    match positional {
+        [value] => text(value).map_or(FluentValue::Error, |s| {
+            let mut c = s.chars();
+            match c.next() {
+                None => String::new(),
+                Some(f) => f.to_uppercase().collect::<String>() + &c.as_str().to_lowercase(),
+            }
+            .into()
+        }),
+        _ => FluentValue::Error,
+    }
+}
+
+/// `TRUNCATE`, keeps the first `length` characters, appending `ellipsis`, by default `…`
+#[cfg(feature = "fn-truncate")]
+pub fn truncate<'a>(positional: &[FluentValue<'a>], named: &FluentArgs) -> FluentValue<'a> {
+    match (positional, named_number(named, "length")) {
+        ([value], Some(length)) => text(value).map_or(FluentValue::Error, |s| {
+            if s.chars().count() <= length {
+                s.into_owned().into()
+            } else {
+                let ellipsis = named_str(named, "ellipsis").unwrap_or("…");
+                s.chars()
+                    .take(length)
+                    .chain(ellipsis.chars())
+                    .collect::<String>()
+                    .into()
+            }
+        }),
+        _ => FluentValue::Error,
+    }
+}
+
+/// `TRIM`, removes leading and trailing whitespace
+#[cfg(feature = "fn-trim")]
+pub fn trim<'a>(positional: &[FluentValue<'a>], _named: &FluentArgs) -> FluentValue<'a> {
+    match positional {
+        [value] => text(value).map_or(FluentValue::Error, |s| s.trim().to_string().into()),
+        _ => FluentValue::Error,
+    }
+}
+
+/// `PAD`, fills with `fill`, by default a space, up to `width` characters, `align` the text
+/// to the `start`, default, or to the `end`
+#[cfg(feature = "fn-pad")]
+pub fn pad<'a>(positional: &[FluentValue<'a>], named: &FluentArgs) -> FluentValue<'a> {
+    let fill = match named_str(named, "fill").map(|f| f.chars()) {
+        Some(mut f) => match (f.next(), f.next()) {
+            (Some(c), None) => c,
+            _ => return FluentValue::Error,
+        },
+        None => ' ',
+    };
+    match (positional, named_number(named, "width")) {
+        ([value], Some(width)) => text(value).map_or(FluentValue::Error, |s| {
+            let padding: String =
+                std::iter::repeat_n(fill, width.saturating_sub(s.chars().count())).collect();
+            match named_str(named, "align") {
+                Some("end") => padding + &s,
+                None | Some("start") => s.into_owned() + &padding,
+                Some(_) => return FluentValue::Error,
+            }
+            .into()
+        }),
+        _ => FluentValue::Error,
+    }
+}
+
+/// `LEN`, number of characters
+#[cfg(feature = "fn-len")]
+pub fn len<'a>(positional: &[FluentValue<'a>], _named: &FluentArgs) -> FluentValue<'a> {
+    match positional {
+        [value] => text(value).map_or(FluentValue::Error, |s| s.chars().count().into()),
+        _ => FluentValue::Error,
+    }
+}
+
+/// Adds the enabled functions to `bundle`, returns [`Error::Overriding`] with the names of
+/// functions the user already added.
+#[allow(unused_variables)] // `bundle` is unused without features
+pub(crate) fn register(bundle: &mut MachineBundle) -> Result<(), Error> {
+    let added: &[(&str, Result<(), FluentError>)] = &[
+        #[cfg(feature = "with-title")]
+        ("TITLE", bundle.add_function("TITLE", title)),
+        #[cfg(feature = "fn-upper")]
+        ("UPPER", bundle.add_function("UPPER", upper)),
+        #[cfg(feature = "fn-lower")]
+        ("LOWER", bundle.add_function("LOWER", lower)),
+        #[cfg(feature = "fn-capitalize")]
+        ("CAPITALIZE", bundle.add_function("CAPITALIZE", capitalize)),
+        #[cfg(feature = "fn-truncate")]
+        ("TRUNCATE", bundle.add_function("TRUNCATE", truncate)),
+        #[cfg(feature = "fn-trim")]
+        ("TRIM", bundle.add_function("TRIM", trim)),
+        #[cfg(feature = "fn-pad")]
+        ("PAD", bundle.add_function("PAD", pad)),
+        #[cfg(feature = "fn-default")]
+        ("DEFAULT", bundle.add_function("DEFAULT", default)),
+        #[cfg(feature = "fn-len")]
+        ("LEN", bundle.add_function("LEN", len)),
+        #[cfg(feature = "fn-number")]
+        ("NUMBER", bundle.add_function("NUMBER", number)),
+        #[cfg(feature = "fn-currency")]
+        ("CURRENCY", bundle.add_function("CURRENCY", currency)),
+        #[cfg(feature = "fn-datetime")]
+        ("DATETIME", bundle.add_function("DATETIME", datetime)),
+        #[cfg(feature = "fn-relative-time")]
+        (
+            "RELATIVE_TIME",
+            bundle.add_function("RELATIVE_TIME", relative_time),
+        ),
+    ];
+    let overriding: Vec<String> = added
+        .iter()
+        .filter(|(_, result)| result.is_err())
+        .map(|(name, _)| name.to_string())
+        .collect();
+    if overriding.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::Overriding(overriding))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "with-title")]
+    #[test]
+    fn title_function() {
+        use super::*;
+
+        let none = FluentArgs::new();
+        assert_eq!(title(&["hELLO".into()], &none), "HELLO".into());
+        assert_eq!(title(&["".into()], &none), "".into());
+        assert!(matches!(title(&[5.into()], &none), FluentValue::Error));
+    }
+
+    #[cfg(all(
+        feature = "fn-upper",
+        feature = "fn-lower",
+        feature = "fn-capitalize",
+        feature = "fn-trim",
+        feature = "fn-len"
+    ))]
+    #[test]
+    fn text_functions() {
+        use super::*;
+
+        let none = FluentArgs::new();
+        assert_eq!(upper(&["Olá".into()], &none), "OLÁ".into());
+        assert_eq!(lower(&["OLÁ".into()], &none), "olá".into());
+        assert_eq!(capitalize(&["hELLO".into()], &none), "Hello".into());
+        assert_eq!(trim(&[" a b  ".into()], &none), "a b".into());
+        assert_eq!(len(&["Olá".into()], &none), 3.into());
+        assert_eq!(upper(&[5.into()], &none), "5".into());
+        assert!(matches!(upper(&[], &none), FluentValue::Error));
+    }
+
+    #[cfg(feature = "fn-truncate")]
+    #[test]
+    fn truncate_function() {
+        use super::*;
+        use crate::f_args;
+
+        let args = f_args!["length" => 3];
+        assert_eq!(truncate(&["Hello".into()], &args), "Hel…".into());
+        assert_eq!(truncate(&["Hel".into()], &args), "Hel".into());
+        let args = f_args!["length" => 2, "ellipsis" => "..."];
+        assert_eq!(truncate(&["Hello".into()], &args), "He...".into());
+        assert!(matches!(
+            truncate(&["Hello".into()], &FluentArgs::new()),
+            FluentValue::Error
+        ));
+    }
+
+    #[cfg(feature = "fn-pad")]
+    #[test]
+    fn pad_function() {
+        use super::*;
+        use crate::f_args;
+
+        let args = f_args!["width" => 5, "fill" => "0", "align" => "end"];
+        assert_eq!(pad(&[42.into()], &args), "00042".into());
+        assert_eq!(pad(&["ab".into()], &f_args!["width" => 4]), "ab  ".into());
+        assert_eq!(
+            pad(&["abcdef".into()], &f_args!["width" => 4]),
+            "abcdef".into()
+        );
+        let args = f_args!["width" => 5, "fill" => "00"];
+        assert!(matches!(pad(&[42.into()], &args), FluentValue::Error));
+    }
+
+    #[cfg(all(feature = "fn-upper", feature = "fn-default", feature = "fn-truncate"))]
+    #[test]
+    fn registered_at_finish() {
+        use crate::{f_args, FluentMachine, LanguageIdentifier};
+
+        let i18n = FluentMachine::build()
+            .add_resource(
+                "en",
+                r#"
+shout = { UPPER($name) }
+greet = Hello { DEFAULT($name, "guest") }
+short = { TRUNCATE($name, length: 3) }
+custom = { BRAND() }
+"#,
+            )
+            .expect("Should add en")
+            .add_function("BRAND", |_, _| "custom".into())
+            .expect("Should add function")
+            .add_resource("pt", r#"custom = { BRAND() }"#)
+            .expect("Should add pt")
+            .set_fallback_locale("pt")
+            .expect("Should set pt")
+            .finish()
+            .expect("Should finish");
+        let en: LanguageIdentifier = "en".parse().unwrap();
+        let pt: LanguageIdentifier = "pt".parse().unwrap();
+        let args = f_args!["name" => "Joana"];
+
+        assert_eq!(
+            i18n.t(&[&en], "shout".try_into().unwrap(), Some(&args)),
+            "JOANA"
+        );
+        assert_eq!(
+            i18n.t(&[&en], "greet".try_into().unwrap(), None),
+            "Hello \u{2068}guest\u{2069}"
+        );
+        assert_eq!(
+            i18n.t(&[&en], "short".try_into().unwrap(), Some(&args)),
+            "Joa…"
+        );
+        assert_eq!(i18n.t(&[&en], "custom".try_into().unwrap(), None), "custom");
+        assert_eq!(
+            i18n.t(&[&pt], "custom".try_into().unwrap(), None),
+            "custom",
+            "`pt` added after the custom function has it too"
+        );
+    }
+
+    #[cfg(all(feature = "fn-upper", feature = "fn-lower"))]
+    #[test]
+    fn builtin_name_collision() {
+        use crate::{Error, FluentMachine};
+
+        let err = FluentMachine::build()
+            .add_resource("en", "a = a")
+            .expect("Should add en")
+            .add_function("LOWER", |_, _| "custom".into())
+            .expect("Should add function")
+            .add_function_for_locale("en", "UPPER", |_, _| "custom".into())
+            .expect("Should add en function")
+            .finish()
+            .err()
+            .expect("Should fail with builtin names");
+        assert!(
+            matches!(&err, Error::Overriding(names) if names == &["UPPER", "LOWER"]),
+            "{err:?}"
+        );
+    }
+}
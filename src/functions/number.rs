@@ -0,0 +1,501 @@
+//! `NUMBER` and the locale aware number formatter.
+
+use std::{borrow::Cow, convert::Infallible, fmt};
+
+use fluent_bundle::types::{
+    FluentNumber, FluentNumberCurrencyDisplayStyle, FluentNumberOptions, FluentNumberStyle,
+};
+use intl_memoizer::Memoizable;
+use intl_pluralrules::{PluralCategory, PluralRuleType, PluralRules};
+
+use super::cldr::{self, locale_data, LocaleData};
+use crate::{
+    types::{FluentLocalized, FormatContext, Localized},
+    FluentArgs, FluentValue, LanguageIdentifier,
+};
+
+/// Number formatter for a locale and a set of options, memoized per bundle.
+///
+/// # Example
+///
+/// ```
+/// use fi18n::functions::NumberFormat;
+/// use fluent_bundle::types::FluentNumberOptions;
+/// use intl_memoizer::Memoizable;
+///
+/// let options = FluentNumberOptions {
+///     minimum_fraction_digits: Some(2),
+///     ..Default::default()
+/// };
+/// let en = NumberFormat::construct("en".parse().unwrap(), options.clone()).unwrap();
+/// let pt = NumberFormat::construct("pt-PT".parse().unwrap(), options).unwrap();
+/// assert_eq!(en.format(5000.5), "5,000.50");
+/// assert_eq!(pt.format(5000.5), "5\u{a0}000,50");
+/// ```
+#[derive(Clone)]
+pub struct NumberFormat {
+    data: &'static LocaleData,
+    options: FluentNumberOptions,
+    // plural rules for currency names
+    plurals: Option<PluralRules>,
+}
+
+impl fmt::Debug for NumberFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NumberFormat")
+            .field("data", &self.data)
+            .field("options", &self.options)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Memoizable for NumberFormat {
+    type Args = FluentNumberOptions;
+    type Error = Infallible;
+
+    fn construct(lang: LanguageIdentifier, options: Self::Args) -> Result<Self, Self::Error> {
+        let plurals = (options.style == FluentNumberStyle::Currency
+            && options.currency_display == FluentNumberCurrencyDisplayStyle::Name)
+            .then(|| PluralRules::create(lang.clone(), PluralRuleType::CARDINAL).ok())
+            .flatten();
+        Ok(Self {
+            data: locale_data(&lang),
+            options,
+            plurals,
+        })
+    }
+}
+
+impl NumberFormat {
+    /// Formats `value` with the locale symbols.
+    pub fn format(&self, value: f64) -> String {
+        if value.is_nan() {
+            return "NaN".to_string();
+        }
+        let symbols = &self.data.number;
+        let style = self.options.style;
+        let currency = match style {
+            FluentNumberStyle::Currency => self.options.currency.as_deref(),
+            _ => None,
+        };
+        let value = if style == FluentNumberStyle::Percent {
+            value * 100.0
+        } else {
+            value
+        };
+        let (int, frac) = if value.is_infinite() {
+            ("∞".to_string(), String::new())
+        } else {
+            self.digits(value.abs(), currency)
+        };
+        let zero = int.chars().chain(frac.chars()).all(|c| c == '0');
+
+        let width = self.options.minimum_integer_digits.unwrap_or(1);
+        let padded = format!("{int:0>width$}");
+        let mut number = String::with_capacity(padded.len() + frac.len() + 4);
+        if self.options.use_grouping {
+            for (i, c) in padded.chars().enumerate() {
+                if i > 0 && (padded.len() - i) % 3 == 0 {
+                    number.push_str(symbols.group);
+                }
+                number.push(c);
+            }
+        } else {
+            number.push_str(&padded);
+        }
+        if !frac.is_empty() {
+            number.push_str(symbols.decimal);
+            number.push_str(&frac);
+        }
+
+        let mut out = String::with_capacity(number.len() + 8);
+        if value.is_sign_negative() && !zero {
+            out.push_str(symbols.minus);
+        }
+        match (style, currency) {
+            (FluentNumberStyle::Percent, _) => {
+                out.push_str(&number);
+                out.push_str(symbols.percent);
+            }
+            (_, Some(code)) => self.push_currency(&mut out, code, &number, &int, &frac),
+            _ => out.push_str(&number),
+        }
+        out
+    }
+
+    // places the currency symbol, code or name around `number`
+    fn push_currency(&self, out: &mut String, code: &str, number: &str, int: &str, frac: &str) {
+        let format = &self.data.currency;
+        let symbol = match self.options.currency_display {
+            FluentNumberCurrencyDisplayStyle::Symbol => {
+                self.data.currency_symbol(code).unwrap_or(code)
+            }
+            FluentNumberCurrencyDisplayStyle::Code => code,
+            FluentNumberCurrencyDisplayStyle::Name => {
+                let operands = if frac.is_empty() {
+                    int.to_string()
+                } else {
+                    format!("{int}.{frac}")
+                };
+                let one = self
+                    .plurals
+                    .as_ref()
+                    .and_then(|p| p.select(operands.as_str()).ok())
+                    == Some(PluralCategory::ONE);
+                out.push_str(number);
+                out.push(' ');
+                out.push_str(self.data.currency_name(code, one).unwrap_or(code));
+                return;
+            }
+        };
+        let adjacent = if format.before {
+            symbol.chars().last()
+        } else {
+            symbol.chars().next()
+        };
+        // letters are never attached to the digits
+        let spacing = match adjacent {
+            Some(c) if format.spacing.is_empty() && c.is_alphabetic() => "\u{a0}",
+            _ => format.spacing,
+        };
+        if format.before {
+            out.push_str(symbol);
+            out.push_str(spacing);
+            out.push_str(number);
+        } else {
+            out.push_str(number);
+            out.push_str(spacing);
+            out.push_str(symbol);
+        }
+    }
+
+    // integer and fraction digits of a positive value, rounded by the options
+    fn digits(&self, value: f64, currency: Option<&str>) -> (String, String) {
+        let o = &self.options;
+        let (digits, point) = decimal(value);
+        if o.minimum_significant_digits.is_some() || o.maximum_significant_digits.is_some() {
+            let max = o.maximum_significant_digits.unwrap_or(21).clamp(1, 21);
+            let min = o.minimum_significant_digits.unwrap_or(1).clamp(1, max);
+            let (digits, point) = round(digits, point, max as isize);
+            let (int, mut frac) = split(&digits, point);
+            let leading = if int == "0" {
+                frac.len() - frac.trim_start_matches('0').len()
+            } else {
+                0
+            };
+            let int_digits = if int == "0" { 0 } else { int.len() };
+            while int_digits + frac.len() - leading < min {
+                frac.push('0');
+            }
+            (int, frac)
+        } else {
+            let (default_min, default_max) = match (o.style, currency) {
+                (FluentNumberStyle::Percent, _) => (0, 0),
+                (_, Some(code)) => {
+                    let digits = cldr::currency(code).map_or(2, |(_, d)| d);
+                    (digits, digits)
+                }
+                _ => (0, 3),
+            };
+            let min = o
+                .minimum_fraction_digits
+                .unwrap_or_else(|| default_min.min(o.maximum_fraction_digits.unwrap_or(20)))
+                .min(20);
+            let max = o
+                .maximum_fraction_digits
+                .unwrap_or(default_max)
+                .clamp(min, 20);
+            let (digits, point) = round(digits, point, point + max as isize);
+            let (int, mut frac) = split(&digits, point);
+            while frac.len() < min {
+                frac.push('0');
+            }
+            (int, frac)
+        }
+    }
+}
+
+// significant digits and decimal point position of the shortest representation,
+// `value = 0.digits × 10^point`
+fn decimal(value: f64) -> (Vec<u8>, isize) {
+    let s = value.to_string();
+    let (int, frac) = s.split_once('.').unwrap_or((&s, ""));
+    let all: Vec<u8> = int.bytes().chain(frac.bytes()).collect();
+    let zeros = all.iter().take_while(|d| **d == b'0').count();
+    let mut digits = all[zeros..].to_vec();
+    while digits.last() == Some(&b'0') {
+        digits.pop();
+    }
+    if digits.is_empty() {
+        return (digits, 0);
+    }
+    (digits, int.len() as isize - zeros as isize)
+}
+
+// keeps `keep` digits, rounding half away from zero like ICU
+fn round(mut digits: Vec<u8>, mut point: isize, keep: isize) -> (Vec<u8>, isize) {
+    if keep < 0 || digits.len() as isize <= keep {
+        return (if keep < 0 { Vec::new() } else { digits }, point);
+    }
+    let up = digits[keep as usize] >= b'5';
+    digits.truncate(keep as usize);
+    if up {
+        let mut carry = true;
+        for d in digits.iter_mut().rev() {
+            if *d == b'9' {
+                *d = b'0';
+            } else {
+                *d += 1;
+                carry = false;
+                break;
+            }
+        }
+        if carry {
+            digits.insert(0, b'1');
+            point += 1;
+        }
+    }
+    while digits.last() == Some(&b'0') {
+        digits.pop();
+    }
+    (digits, point)
+}
+
+// integer and fraction digits, without trailing fraction zeros
+fn split(digits: &[u8], point: isize) -> (String, String) {
+    let digits = std::str::from_utf8(digits).expect("ascii digits");
+    if point <= 0 {
+        let frac = if digits.is_empty() {
+            String::new()
+        } else {
+            "0".repeat(-point as usize) + digits
+        };
+        ("0".to_string(), frac)
+    } else if point as usize >= digits.len() {
+        let int = digits.to_string() + &"0".repeat(point as usize - digits.len());
+        (int, String::new())
+    } else {
+        let (int, frac) = digits.split_at(point as usize);
+        (int.to_string(), frac.to_string())
+    }
+}
+
+/// Number returned by `NUMBER` and `CURRENCY`, formatted with the bundle locale symbols and
+/// its options.
+///
+/// Passed as [`Localized<LocalizedNumber>`](Localized), other numbers keep the fluent
+/// formatting.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocalizedNumber(pub FluentNumber);
+
+impl FluentLocalized for LocalizedNumber {
+    fn format(&self, cx: &FormatContext<'_>) -> Cow<'static, str> {
+        cx.format_number_with(self.0.value, self.0.options.clone())
+            .into()
+    }
+}
+
+impl LocalizedNumber {
+    /// Returns the number of a `NUMBER` or `CURRENCY` result.
+    pub fn from_value<'v>(value: &'v FluentValue) -> Option<&'v FluentNumber> {
+        match value {
+            FluentValue::Custom(c) => c
+                .as_ref()
+                .as_any()
+                .downcast_ref::<Localized<Self>>()
+                .map(|n| &n.0 .0),
+            _ => None,
+        }
+    }
+}
+
+/// Number argument of `NUMBER` and `CURRENCY`, from numbers, numeric strings and their own
+/// results
+pub(crate) fn number_arg(positional: &[FluentValue]) -> Option<FluentNumber> {
+    match positional {
+        [FluentValue::Number(n)] => Some(n.clone()),
+        [FluentValue::String(s)] => s.parse().ok(),
+        [value] => LocalizedNumber::from_value(value).cloned(),
+        _ => None,
+    }
+}
+
+/// `NUMBER`, a number with the formatting options, accepts numbers, numeric strings and
+/// `NUMBER` results
+///
+/// Options are `style` (`decimal`, `percent` or `currency`), `currency`, `currencyDisplay`,
+/// `useGrouping` (`"false"` to disable),
+/// `minimumIntegerDigits`, `minimumFractionDigits`, `maximumFractionDigits`,
+/// `minimumSignificantDigits` and `maximumSignificantDigits`.
+///
+/// Returns a [`LocalizedNumber`], select plural variants on the variable, `{ $n -> ... }`,
+/// as formatted numbers only match the default variant.
+pub fn number<'a>(positional: &[FluentValue<'a>], named: &FluentArgs) -> FluentValue<'a> {
+    let Some(mut number) = number_arg(positional) else {
+        return FluentValue::Error;
+    };
+    number.options.merge(named);
+    Localized(LocalizedNumber(number)).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(lang: &str, value: f64, options: FluentNumberOptions) -> String {
+        NumberFormat::construct(lang.parse().unwrap(), options)
+            .unwrap()
+            .format(value)
+    }
+
+    #[test]
+    fn decimal_format() {
+        let d = FluentNumberOptions::default;
+        assert_eq!(format("en", 5000.5, d()), "5,000.5");
+        assert_eq!(format("pt", 5000.5, d()), "5.000,5");
+        assert_eq!(format("pt-PT", 1234567.0, d()), "1\u{a0}234\u{a0}567");
+        assert_eq!(format("en", 1.0 / 3.0, d()), "0.333");
+        assert_eq!(format("en", -0.0001, d()), "0");
+        assert_eq!(format("en", -12.5, d()), "-12.5");
+        assert_eq!(format("en", 999.9999, d()), "1,000");
+        let o = FluentNumberOptions {
+            use_grouping: false,
+            minimum_fraction_digits: Some(2),
+            ..d()
+        };
+        assert_eq!(format("en", 5000.5, o), "5000.50");
+        let o = FluentNumberOptions {
+            minimum_integer_digits: Some(3),
+            maximum_fraction_digits: Some(1),
+            ..d()
+        };
+        assert_eq!(format("pt", 7.25, o), "007,3");
+    }
+
+    #[test]
+    fn significant_and_percent_format() {
+        let d = FluentNumberOptions::default;
+        let o = FluentNumberOptions {
+            maximum_significant_digits: Some(3),
+            ..d()
+        };
+        assert_eq!(format("en", 123456.0, o.clone()), "123,000");
+        assert_eq!(format("en", 0.012345, o.clone()), "0.0123");
+        assert_eq!(format("en", 9.999, o), "10");
+        let o = FluentNumberOptions {
+            maximum_fraction_digits: Some(2),
+            ..d()
+        };
+        assert_eq!(format("en", 1.005, o.clone()), "1.01");
+        assert_eq!(format("en", 0.004, o), "0");
+        let o = FluentNumberOptions {
+            minimum_significant_digits: Some(3),
+            ..d()
+        };
+        assert_eq!(format("pt", 1.5, o), "1,50");
+        let o = FluentNumberOptions {
+            style: FluentNumberStyle::Percent,
+            ..d()
+        };
+        assert_eq!(format("en", 0.256, o.clone()), "26%");
+        assert_eq!(format("de", 0.256, o), "26\u{a0}%");
+    }
+
+    #[test]
+    fn currency_format() {
+        let currency = |code: &str, display: &str| FluentNumberOptions {
+            style: FluentNumberStyle::Currency,
+            currency: Some(code.to_string()),
+            currency_display: display.into(),
+            ..Default::default()
+        };
+        assert_eq!(format("en", 5.0, currency("USD", "symbol")), "$5.00");
+        assert_eq!(
+            format("pt", 5.0, currency("USD", "symbol")),
+            "US$\u{a0}5,00"
+        );
+        assert_eq!(format("en", 5.0, currency("EUR", "code")), "EUR\u{a0}5.00");
+        assert_eq!(format("de", 5.0, currency("EUR", "code")), "5,00\u{a0}EUR");
+        assert_eq!(format("en", 1234.5, currency("JPY", "symbol")), "¥1,235");
+        assert_eq!(
+            format("en", 5.0, currency("XTS", "symbol")),
+            "XTS\u{a0}5.00"
+        );
+        assert_eq!(
+            format("en", 2.0, currency("USD", "name")),
+            "2.00 US dollars"
+        );
+        assert_eq!(format("de", 2.0, currency("USD", "name")), "2,00 USD");
+        let o = FluentNumberOptions {
+            maximum_fraction_digits: Some(0),
+            ..currency("EUR", "symbol")
+        };
+        assert_eq!(format("pt-PT", 5.0, o), "5\u{a0}€");
+    }
+
+    #[test]
+    fn number_function() {
+        use crate::f_args;
+
+        let args = f_args!["minimumFractionDigits" => 2, "useGrouping" => "false"];
+        let value = number(&["5000.5".into()], &args);
+        let n = LocalizedNumber::from_value(&value).expect("Should be a localized number");
+        assert_eq!(n.value, 5000.5);
+        assert_eq!(n.options.minimum_fraction_digits, Some(2));
+        assert_eq!(n.options.minimum_integer_digits, None);
+        assert!(!n.options.use_grouping);
+        let value = number(&[value], &f_args!["useGrouping" => "true"]);
+        let n = LocalizedNumber::from_value(&value).expect("Should accept NUMBER results");
+        assert_eq!(n.options.minimum_fraction_digits, Some(2));
+        assert!(n.options.use_grouping);
+        assert!(matches!(
+            number(&["five".into()], &args),
+            FluentValue::Error
+        ));
+    }
+
+    #[test]
+    fn localized_at_finish() {
+        use crate::{f_args, FluentMachine, LanguageIdentifier};
+
+        let ftl = r#"
+total = { NUMBER($n, minimumFractionDigits: 2) }
+plain = { $n }
+grouping = { NUMBER($n, useGrouping: "false") }
+digits = { NUMBER($n, minimumIntegerDigits: 5) }
+emails = { $n ->
+    [one] One email
+   *[other] { NUMBER($n) } emails
+}
+"#;
+        let i18n = FluentMachine::build()
+            .add_resource("en", ftl)
+            .expect("Should add en")
+            .add_resource("pt-PT", ftl)
+            .expect("Should add pt-PT")
+            .set_fallback_locale("en")
+            .expect("Should set en")
+            .finish()
+            .expect("Should finish");
+        let en: LanguageIdentifier = "en".parse().unwrap();
+        let pt: LanguageIdentifier = "pt-PT".parse().unwrap();
+        let args = f_args!["n" => 5000.5];
+
+        let t = |lang, key: &str| i18n.t(&[lang], key.try_into().unwrap(), Some(&args));
+        assert_eq!(t(&en, "total"), "5,000.50");
+        assert_eq!(t(&pt, "total"), "5\u{a0}000,50");
+        assert_eq!(t(&en, "plain"), "5000.5", "plain numbers are not localized");
+        assert_eq!(t(&pt, "plain"), "5000.5", "plain numbers are not localized");
+        assert_eq!(t(&pt, "grouping"), "5000,5");
+        assert_eq!(t(&pt, "digits"), "05\u{a0}000,5");
+        assert_eq!(t(&pt, "emails"), "\u{2068}5\u{a0}000,5\u{2069} emails");
+        assert_eq!(
+            i18n.t(
+                &[&en],
+                "emails".try_into().unwrap(),
+                Some(&f_args!["n" => 1])
+            ),
+            "One email"
+        );
+    }
+}
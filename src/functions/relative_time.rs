@@ -0,0 +1,377 @@
+//! `RELATIVE_TIME` and [`FluentRelativeTime`], durations like `3 days ago`.
+
+use std::{borrow::Cow, convert::Infallible, fmt};
+
+use fluent_bundle::types::{FluentNumberOptions, FluentType};
+use intl_memoizer::Memoizable;
+use intl_pluralrules::{PluralCategory, PluralRuleType, PluralRules};
+
+use super::{
+    cldr::{locale_data, RelativeSymbols},
+    NumberFormat,
+};
+use crate::{FluentArgs, FluentValue, LanguageIdentifier};
+
+/// Units of a relative time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RelativeTimeUnit {
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+impl RelativeTimeUnit {
+    fn parse(unit: &str) -> Option<Self> {
+        match unit.strip_suffix('s').unwrap_or(unit) {
+            "second" => Some(Self::Second),
+            "minute" => Some(Self::Minute),
+            "hour" => Some(Self::Hour),
+            "day" => Some(Self::Day),
+            "week" => Some(Self::Week),
+            "month" => Some(Self::Month),
+            "year" => Some(Self::Year),
+            _ => None,
+        }
+    }
+
+    /// Length in seconds, months and years use the gregorian average.
+    pub fn seconds(self) -> f64 {
+        match self {
+            Self::Second => 1.0,
+            Self::Minute => 60.0,
+            Self::Hour => 3_600.0,
+            Self::Day => 86_400.0,
+            Self::Week => 604_800.0,
+            Self::Month => 2_629_746.0,
+            Self::Year => 31_556_952.0,
+        }
+    }
+
+    /// Largest unit that fits in `seconds`.
+    pub fn select(seconds: f64) -> Self {
+        [
+            Self::Year,
+            Self::Month,
+            Self::Week,
+            Self::Day,
+            Self::Hour,
+            Self::Minute,
+        ]
+        .into_iter()
+        .find(|unit| seconds.abs() >= unit.seconds())
+        .unwrap_or(Self::Second)
+    }
+}
+
+/// Formatting options of a [`FluentRelativeTime`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct RelativeTimeOptions {
+    /// unit to use, selected from the duration by default
+    pub unit: Option<RelativeTimeUnit>,
+    /// use names like `yesterday` instead of `1 day ago`
+    pub numeric_auto: bool,
+}
+
+impl RelativeTimeOptions {
+    /// Merges `unit` and `numeric` from the function named arguments.
+    pub fn merge(&mut self, named: &FluentArgs) {
+        for (key, value) in named.iter() {
+            match (key, value) {
+                ("unit", FluentValue::String(s)) => {
+                    self.unit = RelativeTimeUnit::parse(s).or(self.unit);
+                }
+                ("numeric", FluentValue::String(s)) => {
+                    self.numeric_auto = s == "auto";
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// A duration in seconds relative to now, negative in the past.
+///
+/// # Example
+///
+/// ```
+/// use fi18n::{f_args, functions::FluentRelativeTime};
+///
+/// let args = f_args!["posted" => FluentRelativeTime::new(-3 * 86_400)];
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct FluentRelativeTime {
+    seconds: i64,
+    options: RelativeTimeOptions,
+}
+
+impl FluentRelativeTime {
+    pub fn new(seconds: i64) -> Self {
+        Self {
+            seconds,
+            options: RelativeTimeOptions::default(),
+        }
+    }
+
+    /// Sets the formatting options, overridden by `RELATIVE_TIME` named arguments.
+    pub fn with_options(mut self, options: RelativeTimeOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    pub fn seconds(&self) -> i64 {
+        self.seconds
+    }
+
+    pub fn options(&self) -> &RelativeTimeOptions {
+        &self.options
+    }
+}
+
+impl<'a> From<FluentRelativeTime> for FluentValue<'a> {
+    fn from(value: FluentRelativeTime) -> Self {
+        FluentValue::Custom(Box::new(value))
+    }
+}
+
+impl FluentType for FluentRelativeTime {
+    fn duplicate(&self) -> Box<dyn FluentType + Send> {
+        Box::new(self.clone())
+    }
+
+    fn as_string(&self, intls: &intl_memoizer::IntlLangMemoizer) -> Cow<'static, str> {
+        intls
+            .with_try_get::<RelativeTimeFormat, _, _>(self.options.clone(), |f| {
+                f.format(self.seconds)
+            })
+            .unwrap_or_else(|e| match e {})
+            .into()
+    }
+
+    fn as_string_threadsafe(
+        &self,
+        intls: &intl_memoizer::concurrent::IntlLangMemoizer,
+    ) -> Cow<'static, str> {
+        intls
+            .with_try_get::<RelativeTimeFormat, _, _>(self.options.clone(), |f| {
+                f.format(self.seconds)
+            })
+            .unwrap_or_else(|e| match e {})
+            .into()
+    }
+}
+
+/// Relative time formatter for a locale and a set of options, memoized per bundle.
+///
+/// Picks the unit from the duration and the plural form from the locale plural rules.
+#[derive(Clone)]
+pub struct RelativeTimeFormat {
+    symbols: &'static RelativeSymbols,
+    number: NumberFormat,
+    plurals: Option<PluralRules>,
+    options: RelativeTimeOptions,
+}
+
+impl fmt::Debug for RelativeTimeFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RelativeTimeFormat")
+            .field("symbols", &self.symbols)
+            .field("options", &self.options)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Memoizable for RelativeTimeFormat {
+    type Args = RelativeTimeOptions;
+    type Error = Infallible;
+
+    fn construct(lang: LanguageIdentifier, options: Self::Args) -> Result<Self, Self::Error> {
+        let number = NumberFormat::construct(lang.clone(), FluentNumberOptions::default())?;
+        Ok(Self {
+            symbols: &locale_data(&lang).relative,
+            number,
+            plurals: PluralRules::create(lang, PluralRuleType::CARDINAL).ok(),
+            options,
+        })
+    }
+}
+
+impl RelativeTimeFormat {
+    /// Formats a duration of `seconds`, negative in the past.
+    pub fn format(&self, seconds: i64) -> String {
+        let past = seconds < 0;
+        let abs = seconds.unsigned_abs() as f64;
+        let unit = self
+            .options
+            .unit
+            .unwrap_or_else(|| RelativeTimeUnit::select(abs));
+        let count = (abs / unit.seconds()).round() as u64;
+
+        if self.options.numeric_auto {
+            let named = match unit {
+                RelativeTimeUnit::Second if count == 0 => return self.symbols.now.to_string(),
+                RelativeTimeUnit::Day => Some(0),
+                RelativeTimeUnit::Week => Some(1),
+                RelativeTimeUnit::Month => Some(2),
+                RelativeTimeUnit::Year => Some(3),
+                _ => None,
+            };
+            if let (Some(i), 0 | 1) = (named, count) {
+                let which = match (count, past) {
+                    (0, _) => 1,
+                    (_, true) => 0,
+                    (_, false) => 2,
+                };
+                return self.symbols.named[i][which].to_string();
+            }
+        }
+
+        let one =
+            self.plurals.as_ref().and_then(|p| p.select(count).ok()) == Some(PluralCategory::ONE);
+        let form = usize::from(past) * 2 + usize::from(!one);
+        self.symbols.units[unit as usize][form].replace("{0}", &self.number.format(count as f64))
+    }
+}
+
+/// `RELATIVE_TIME`, a duration relative to now in the bundle locale, accepts a
+/// [`FluentRelativeTime`] or a number of seconds, negative in the past
+///
+/// Options are `unit` (`second` to `year`, selected from the duration by default) and
+/// `numeric` (`auto` for names like `yesterday`, or `always`).
+pub fn relative_time<'a>(positional: &[FluentValue<'a>], named: &FluentArgs) -> FluentValue<'a> {
+    let mut value = match positional {
+        [FluentValue::Custom(c)] => {
+            match c.as_ref().as_any().downcast_ref::<FluentRelativeTime>() {
+                Some(value) => value.clone(),
+                None => return FluentValue::Error,
+            }
+        }
+        [FluentValue::Number(n)] if n.value.is_finite() => {
+            FluentRelativeTime::new(n.value.round() as i64)
+        }
+        _ => return FluentValue::Error,
+    };
+    value.options.merge(named);
+    value.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::f_args;
+
+    const HOUR: i64 = 3_600;
+    const DAY: i64 = 86_400;
+
+    fn format(lang: &str, seconds: i64, auto: bool) -> String {
+        let options = RelativeTimeOptions {
+            numeric_auto: auto,
+            ..Default::default()
+        };
+        RelativeTimeFormat::construct(lang.parse().unwrap(), options)
+            .unwrap()
+            .format(seconds)
+    }
+
+    #[test]
+    fn unit_selection() {
+        assert_eq!(RelativeTimeUnit::select(0.0), RelativeTimeUnit::Second);
+        assert_eq!(RelativeTimeUnit::select(-59.0), RelativeTimeUnit::Second);
+        assert_eq!(RelativeTimeUnit::select(-60.0), RelativeTimeUnit::Minute);
+        assert_eq!(RelativeTimeUnit::select(7_200.0), RelativeTimeUnit::Hour);
+        assert_eq!(
+            RelativeTimeUnit::select(13.0 * 86_400.0),
+            RelativeTimeUnit::Week
+        );
+        assert_eq!(
+            RelativeTimeUnit::select(40.0 * 86_400.0),
+            RelativeTimeUnit::Month
+        );
+        assert_eq!(
+            RelativeTimeUnit::select(400.0 * 86_400.0),
+            RelativeTimeUnit::Year
+        );
+        assert_eq!(RelativeTimeUnit::parse("days"), Some(RelativeTimeUnit::Day));
+    }
+
+    #[test]
+    fn numeric_always() {
+        assert_eq!(format("en", 2 * HOUR, false), "in 2 hours");
+        assert_eq!(format("en", -DAY, false), "1 day ago");
+        assert_eq!(format("en", -3 * DAY, false), "3 days ago");
+        assert_eq!(format("pt", -3 * DAY, false), "há 3 dias");
+        assert_eq!(format("pt", -DAY, false), "há 1 dia");
+        assert_eq!(format("pt", 90, false), "em 2 minutos");
+        assert_eq!(format("pt-PT", HOUR, false), "dentro de 1 hora");
+        assert_eq!(format("de", -3 * DAY, false), "vor 3 Tagen");
+        assert_eq!(format("it", -2 * HOUR, false), "2 ore fa");
+        assert_eq!(format("en", -1500 * 31_556_952, false), "1,500 years ago");
+    }
+
+    #[test]
+    fn numeric_auto() {
+        assert_eq!(format("en", -DAY, true), "yesterday");
+        assert_eq!(format("pt", DAY, true), "amanhã");
+        assert_eq!(format("pt-PT", 0, true), "agora");
+        assert_eq!(format("en", -2 * DAY, true), "2 days ago");
+        assert_eq!(format("en", -HOUR, true), "1 hour ago");
+        assert_eq!(format("fr", -7 * DAY, true), "la semaine dernière");
+    }
+
+    #[test]
+    fn relative_time_function() {
+        let args = f_args!["unit" => "hour", "numeric" => "auto"];
+        let FluentValue::Custom(c) = relative_time(&[(-5400.4).into()], &args) else {
+            panic!("Should be a relative time");
+        };
+        let value = c
+            .as_ref()
+            .as_any()
+            .downcast_ref::<FluentRelativeTime>()
+            .unwrap();
+        assert_eq!(value.seconds(), -5400);
+        assert_eq!(value.options().unit, Some(RelativeTimeUnit::Hour));
+        assert!(value.options().numeric_auto);
+        assert!(matches!(
+            relative_time(&["soon".into()], &args),
+            FluentValue::Error
+        ));
+    }
+
+    #[test]
+    fn localized_at_finish() {
+        use crate::{FluentMachine, LanguageIdentifier};
+
+        let ftl = r#"
+posted = Posted { RELATIVE_TIME($delta, numeric: "auto") }
+"#;
+        let i18n = FluentMachine::build()
+            .add_resource("en", ftl)
+            .expect("Should add en")
+            .add_resource("pt", r#"posted = Publicado { RELATIVE_TIME($delta) }"#)
+            .expect("Should add pt")
+            .set_fallback_locale("en")
+            .expect("Should set en")
+            .finish()
+            .expect("Should finish");
+        let en: LanguageIdentifier = "en".parse().unwrap();
+        let pt: LanguageIdentifier = "pt".parse().unwrap();
+        let t = |lang, args| i18n.t(&[lang], "posted".try_into().unwrap(), Some(&args));
+
+        assert_eq!(
+            t(&en, f_args!["delta" => -DAY]),
+            "Posted \u{2068}yesterday\u{2069}"
+        );
+        assert_eq!(
+            t(&pt, f_args!["delta" => -3 * DAY]),
+            "Publicado \u{2068}há 3 dias\u{2069}"
+        );
+        assert_eq!(
+            t(&pt, f_args!["delta" => FluentRelativeTime::new(2 * HOUR)]),
+            "Publicado \u{2068}em 2 horas\u{2069}"
+        );
+    }
+}
@@ -0,0 +1,364 @@
+/*!
+HTML safe translations, escaping argument values but not the translator markup
+*/
+
+use std::{cell::Cell, fmt};
+
+use intl_memoizer::concurrent;
+
+use crate::{Fkey, FluentArgs, FluentMachine, FluentValue, LanguageIdentifier};
+
+/// HTML already escaped, safe to render without escaping again.
+///
+/// Template engines should render it raw, for example with the `safe` filter.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct SafeHtml(String);
+
+impl SafeHtml {
+    /// Wraps trusted HTML, without escaping.
+    pub fn from_trusted(html: String) -> Self {
+        Self(html)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+impl fmt::Display for SafeHtml {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl AsRef<str> for SafeHtml {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<SafeHtml> for String {
+    fn from(html: SafeHtml) -> Self {
+        html.0
+    }
+}
+
+/// Escapes `&`, `<`, `>`, `"` and `'` to HTML entities.
+///
+/// # Example
+/// ```
+/// assert_eq!(
+///     fi18n::html::escape(r#"<a href="/">'&'</a>"#),
+///     "&lt;a href=&quot;/&quot;&gt;&#39;&amp;&#39;&lt;/a&gt;"
+/// );
+/// ```
+pub fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+thread_local! {
+    // set while `t_html` writes a pattern on this thread, check `format_value`
+    static ESCAPING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Sets [`ESCAPING`] until dropped, restoring the previous value
+struct Escaping(bool);
+
+impl Escaping {
+    fn set(on: bool) -> Self {
+        Self(ESCAPING.with(|e| e.replace(on)))
+    }
+}
+
+impl Drop for Escaping {
+    fn drop(&mut self) {
+        ESCAPING.with(|e| e.set(self.0));
+    }
+}
+
+/// Bundle formatter, escapes the values written while [`FluentMachine::t_html`] writes a
+/// pattern, those of variables, function results and number literals.
+///
+/// The pattern text, string literals and referenced messages and terms are written as they
+/// are, functions get the raw arguments.
+pub(crate) fn format_value(
+    value: &FluentValue,
+    intls: &concurrent::IntlLangMemoizer,
+) -> Option<String> {
+    if !ESCAPING.with(Cell::get) {
+        return None;
+    }
+    match value {
+        FluentValue::String(s) => Some(escape(s)),
+        FluentValue::Custom(c) => {
+            // custom values formatting translations of their own get them unescaped
+            let raw = {
+                let _unescaped = Escaping::set(false);
+                c.as_string_threadsafe(intls)
+            };
+            Some(escape(&raw))
+        }
+        FluentValue::Number(_) | FluentValue::Error | FluentValue::None => None,
+    }
+}
+
+impl FluentMachine {
+    /// Returns translation as [`FluentMachine::t`], HTML escaping the values of arguments
+    /// and function results as they are written, custom values after being formatted, while
+    /// markup from the `ftl` sources is kept as trusted.
+    ///
+    /// Functions get the arguments unescaped, `TRUNCATE` and `LEN` count the characters of
+    /// the original values. Default arguments are escaped as well.
+    ///
+    /// Missing keys output, according to [`MissingKeyPolicy`](crate::MissingKeyPolicy), is escaped.
+    ///
+    /// # Panics
+    ///
+    /// On a missing key, according to [`MissingKeyPolicy`](crate::MissingKeyPolicy), check
+    /// [`FluentMachine::t`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use fi18n::{f_args, FluentMachine};
+    ///
+    /// let i18n = FluentMachine::build()
+    ///     .add_resource("en", r#"welcome = Welcome <b>{ $username }</b>"#).expect("Should add en")
+    ///     .finish().expect("Should finish");
+    /// let en = i18n.negotiate_languages("en");
+    ///
+    /// let html = i18n.t_html(
+    ///     &en,
+    ///     "welcome".try_into().unwrap(),
+    ///     Some(&f_args!["username" => "<script>alert(1)</script>"]),
+    /// );
+    /// assert_eq!(
+    ///     html.as_str(),
+    ///     "Welcome <b>\u{2068}&lt;script&gt;alert(1)&lt;/script&gt;\u{2069}</b>"
+    /// );
+    /// ```
+    pub fn t_html(
+        &self,
+        negotiated: &[&LanguageIdentifier],
+        path: Fkey,
+        args: Option<&FluentArgs>,
+    ) -> SafeHtml {
+        let merged = self.merge_default_args(args);
+        let args = merged.get();
+        let mut out = String::new();
+        match self.resolve(negotiated, path) {
+            Ok((locale, bundle, pattern)) => {
+                let filled = Self::fill_defaulted(bundle, pattern, args);
+                let args = filled.as_ref().or(args);
+                let mut errors: Vec<_> = vec![];
+                {
+                    let _escaping = Escaping::set(true);
+                    // writing to a `String` never fails
+                    let _ = bundle.write_pattern(&mut out, pattern, args, &mut errors);
+                }
+                self.report_format_errors(path, locale, &errors);
+                SafeHtml(out)
+            }
+            Err(_) => {
+                // writing to a `String` never fails
+                let _ = self.write_missing(&mut out, negotiated, path, args);
+                SafeHtml(escape(&out))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        f_args,
+        types::{FluentLocalized, FormatContext, Localized},
+        FluentMachine, FluentValue, LanguageIdentifier, MissingKeyPolicy,
+    };
+    use std::{borrow::Cow, sync::Arc};
+
+    #[test]
+    fn escapes_arguments_only() {
+        let i18n = FluentMachine::build()
+            .add_resource(
+                "en",
+                r#"
+-brand = <i>Example</i>
+inbox = <a href="/inbox">{ $name }</a> has { $count } messages at { -brand }
+unread = { $count ->
+    [one] <b>One</b> message for { $name }
+   *[other] <b>{ $count }</b> messages
+}
+"#,
+            )
+            .expect("Should add en")
+            .set_missing_key_policy(MissingKeyPolicy::Callback(Arc::new(
+                |key, args, _| match args.and_then(|a| a.get("name")) {
+                    Some(FluentValue::String(name)) => format!("<{key}> {name}"),
+                    _ => format!("<{key}>"),
+                },
+            )))
+            .finish()
+            .unwrap();
+        let en: LanguageIdentifier = "en".parse().unwrap();
+
+        assert_eq!(
+            i18n.t_html(
+                &[&en],
+                "inbox".try_into().unwrap(),
+                Some(&f_args!["name" => "Tom & \"Jerry\"", "count" => 3])
+            )
+            .to_string(),
+            "<a href=\"/inbox\">\u{2068}Tom &amp; &quot;Jerry&quot;\u{2069}</a> has \u{2068}3\u{2069} messages at <i>Example</i>"
+        );
+        assert_eq!(
+            i18n.t_html(
+                &[&en],
+                "unread".try_into().unwrap(),
+                Some(&f_args!["name" => "<i>", "count" => 1])
+            )
+            .into_string(),
+            "<b>One</b> message for \u{2068}&lt;i&gt;\u{2069}"
+        );
+        assert_eq!(
+            i18n.t_html(&[&en], "missing".try_into().unwrap(), None)
+                .into_string(),
+            "&lt;missing&gt;"
+        );
+        assert_eq!(
+            i18n.t_html(
+                &[&en],
+                "missing".try_into().unwrap(),
+                Some(&f_args!["name" => "Tom & Jerry"])
+            )
+            .into_string(),
+            "&lt;missing&gt; Tom &amp; Jerry",
+            "the callback gets the raw arguments, escaped once"
+        );
+    }
+
+    #[test]
+    fn escapes_default_arguments() {
+        let i18n = FluentMachine::build()
+            .add_resource("en", r#"footer = <small>{ $company } { $year }</small>"#)
+            .expect("Should add en")
+            .set_default_args(&f_args!["company" => "Tom & Jerry", "year" => "2026"])
+            .finish()
+            .unwrap();
+        let en: LanguageIdentifier = "en".parse().unwrap();
+
+        assert_eq!(
+            i18n.t_html(&[&en], "footer".try_into().unwrap(), None)
+                .into_string(),
+            "<small>\u{2068}Tom &amp; Jerry\u{2069} \u{2068}2026\u{2069}</small>"
+        );
+        assert_eq!(
+            i18n.localize_t("en")
+                .with_default_args(&f_args!["year" => "2027"])
+                .t_html("footer".try_into().unwrap(), None)
+                .into_string(),
+            "<small>\u{2068}Tom &amp; Jerry\u{2069} \u{2068}2027\u{2069}</small>"
+        );
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Signature(&'static str);
+
+    impl FluentLocalized for Signature {
+        fn format(&self, _: &FormatContext<'_>) -> Cow<'static, str> {
+            self.0.into()
+        }
+    }
+
+    #[test]
+    fn escapes_custom_arguments() {
+        let i18n = FluentMachine::build()
+            .add_resource("en", r#"signed = <p>{ $signature }</p>"#)
+            .expect("Should add en")
+            .finish()
+            .unwrap();
+        let en: LanguageIdentifier = "en".parse().unwrap();
+        let args = f_args!["signature" => Localized(Signature("<script>alert(1)</script>"))];
+
+        assert_eq!(
+            i18n.t_html(&[&en], "signed".try_into().unwrap(), Some(&args))
+                .into_string(),
+            "<p>\u{2068}&lt;script&gt;alert(1)&lt;/script&gt;\u{2069}</p>"
+        );
+        assert_eq!(
+            i18n.t(&[&en], "signed".try_into().unwrap(), Some(&args)),
+            "<p>\u{2068}<script>alert(1)</script>\u{2069}</p>"
+        );
+    }
+
+    #[cfg(all(
+        feature = "fn-truncate",
+        feature = "fn-len",
+        feature = "fn-pad",
+        feature = "fn-upper"
+    ))]
+    #[test]
+    fn functions_get_unescaped_arguments() {
+        let i18n = FluentMachine::build()
+            .add_resource(
+                "en",
+                r#"
+short = <b>{ TRUNCATE($name, length: 5) }</b>
+length = { LEN($name) } characters
+padded = <pre>{ PAD($name, width: 13, fill: ".") }</pre>
+upper = <b>{ UPPER($name) }</b>
+"#,
+            )
+            .expect("Should add en")
+            .finish()
+            .unwrap();
+        let en: LanguageIdentifier = "en".parse().unwrap();
+        let args = f_args!["name" => "Tom & Jerry"];
+        let t = |key: &str| {
+            i18n.t_html(&[&en], key.try_into().unwrap(), Some(&args))
+                .into_string()
+        };
+
+        assert_eq!(t("short"), "<b>\u{2068}Tom &amp;…\u{2069}</b>");
+        assert_eq!(t("length"), "\u{2068}11\u{2069} characters");
+        assert_eq!(t("padded"), "<pre>\u{2068}Tom &amp; Jerry..\u{2069}</pre>");
+        assert_eq!(t("upper"), "<b>\u{2068}TOM &amp; JERRY\u{2069}</b>");
+    }
+
+    #[cfg(feature = "fn-datetime")]
+    #[test]
+    fn functions_accept_custom_arguments() {
+        use crate::functions::FluentDateTime;
+
+        let i18n = FluentMachine::build()
+            .add_resource(
+                "en",
+                r#"date = <time>{ DATETIME($date, dateStyle: "short") }</time>"#,
+            )
+            .expect("Should add en")
+            .finish()
+            .unwrap();
+        let en: LanguageIdentifier = "en".parse().unwrap();
+        let args = f_args!["date" => FluentDateTime::from_unix(0)];
+
+        assert_eq!(
+            i18n.t_html(&[&en], "date".try_into().unwrap(), Some(&args))
+                .into_string(),
+            i18n.t(&[&en], "date".try_into().unwrap(), Some(&args))
+        );
+    }
+}
@@ -0,0 +1,176 @@
+/*!
+Inspect the messages of a [`FluentMachine`]
+*/
+
+use fluent_syntax::{
+    ast::{Entry, Message, Resource},
+    serializer,
+};
+use std::collections::BTreeSet;
+
+use crate::{Fkey, FluentMachine, LanguageIdentifier};
+
+impl FluentMachine {
+    /// Returns the sorted message ids of `locale`, `None` if `locale` is not available, or
+    /// was loaded without sources, check [`FluentMachineLoader::load_resources`](crate::FluentMachineLoader::load_resources).
+    ///
+    /// # Example
+    /// ```rust
+    /// use fi18n::{FluentMachine, LanguageIdentifier};
+    ///
+    /// let i18n = FluentMachine::build()
+    ///     .add_resource("en", r#"
+    /// -brand = Example
+    /// login = Login
+    ///     .username = Username
+    /// hello = Hello"#).expect("Should add en")
+    ///     .finish().expect("Should finish");
+    /// let en: LanguageIdentifier = "en".parse().unwrap();
+    ///
+    /// assert_eq!(i18n.message_ids(&en), Some(vec!["hello", "login"]));
+    /// assert!(i18n.has_message(&en, "login.username".try_into().unwrap()));
+    /// assert_eq!(i18n.attributes(&en, "login"), Some(vec!["username"]));
+    /// assert_eq!(
+    ///     i18n.message_source(&en, "login").as_deref(),
+    ///     Some("login = Login\n    .username = Username\n")
+    /// );
+    /// ```
+    pub fn message_ids(&self, locale: &LanguageIdentifier) -> Option<Vec<&str>> {
+        let ids: BTreeSet<&str> = self
+            .resources
+            .get(locale)?
+            .iter()
+            .flat_map(|r| r.entries())
+            .filter_map(|entry| match entry {
+                Entry::Message(msg) => Some(msg.id.name),
+                _ => None,
+            })
+            .collect();
+        Some(ids.into_iter().collect())
+    }
+
+    /// Returns `true` if `locale` has the message, and the attribute if present in `path`.
+    pub fn has_message(&self, locale: &LanguageIdentifier, path: Fkey) -> bool {
+        self.bundles
+            .get(locale)
+            .and_then(|bundle| bundle.get_message(path.0))
+            .is_some_and(|msg| match path.1 {
+                Some(attr) => msg.get_attribute(attr).is_some(),
+                None => true,
+            })
+    }
+
+    /// Returns the attribute ids of `message`, `None` if `locale` doesn't have the message.
+    pub fn attributes(&self, locale: &LanguageIdentifier, message: &str) -> Option<Vec<&str>> {
+        self.message_entry(locale, message)
+            .map(|msg| msg.attributes.iter().map(|a| a.id.name).collect())
+    }
+
+    /// Returns the FTL source of `message`, as used by `locale`, `None` if `locale`
+    /// doesn't have the message.
+    pub fn message_source(&self, locale: &LanguageIdentifier, message: &str) -> Option<String> {
+        self.message_entry(locale, message).map(|msg| {
+            serializer::serialize(&Resource {
+                body: vec![Entry::Message(msg.clone())],
+            })
+        })
+    }
+
+    /// Returns the message entry used by the bundle, the last added overrides the previous.
+    pub(crate) fn message_entry(
+        &self,
+        locale: &LanguageIdentifier,
+        message: &str,
+    ) -> Option<&Message<&str>> {
+        self.resources
+            .get(locale)?
+            .iter()
+            .rev()
+            .flat_map(|r| r.entries())
+            .find_map(|entry| match entry {
+                Entry::Message(msg) if msg.id.name == message => Some(msg),
+                _ => None,
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        builders::InheritanceSyntaxErrorHandling, loaders::DirectoryLoader, FluentMachine,
+        LanguageIdentifier,
+    };
+
+    #[test]
+    fn introspects_inherited_and_overridden_messages() {
+        let i18n = FluentMachine::build_with_inheritance(InheritanceSyntaxErrorHandling::AtBuild)
+            .add_source("", "company = Example", None)
+            .expect("Should add global")
+            .add_source("en", "region = International\nlanguage = English", None)
+            .expect("Should add en")
+            .add_source("en-US", "region = United States\n    .short = US", None)
+            .expect("Should add en-US")
+            .build_inheritance()
+            .expect("Should build")
+            .finish()
+            .expect("Should finish");
+        let us: LanguageIdentifier = "en-US".parse().unwrap();
+        let pt: LanguageIdentifier = "pt".parse().unwrap();
+
+        assert_eq!(
+            i18n.message_ids(&us),
+            Some(vec!["company", "language", "region"])
+        );
+        assert_eq!(i18n.message_ids(&pt), None);
+        assert_eq!(i18n.attributes(&us, "region"), Some(vec!["short"]));
+        assert_eq!(i18n.attributes(&us, "missing"), None);
+        assert_eq!(
+            i18n.message_source(&us, "region").as_deref(),
+            Some("region = United States\n    .short = US\n")
+        );
+        assert!(i18n.has_message(&us, "company".try_into().unwrap()));
+        assert!(!i18n.has_message(&us, "company.short".try_into().unwrap()));
+        assert!(!i18n.has_message(&pt, "company".try_into().unwrap()));
+    }
+
+    #[test]
+    fn introspects_directory_loader() {
+        let i18n = FluentMachine::build_loader(DirectoryLoader::new("examples/locales/"))
+            .unwrap()
+            .finish()
+            .expect("failed to create FluentMachine");
+        let en: LanguageIdentifier = "en".parse().unwrap();
+        let uk: LanguageIdentifier = "en-UK".parse().unwrap();
+
+        assert_eq!(i18n.message_ids(&en), i18n.message_ids(&uk));
+        assert_eq!(
+            i18n.attributes(&en, "login"),
+            Some(vec!["username", "help-text", "not-found"])
+        );
+    }
+
+    #[test]
+    fn loader_without_resources() {
+        use crate::{machine::MachineBundles, Error, FluentMachineLoader};
+
+        struct Bundles;
+
+        impl FluentMachineLoader for Bundles {
+            fn load(&self) -> Result<MachineBundles, Error> {
+                Ok(FluentMachine::build()
+                    .add_resource("en", "hello = Hello")?
+                    .bundles)
+            }
+        }
+
+        let i18n = FluentMachine::build_loader(Bundles)
+            .unwrap()
+            .finish()
+            .expect("Should finish");
+        let en: LanguageIdentifier = "en".parse().unwrap();
+
+        assert_eq!(i18n.t(&[&en], "hello".try_into().unwrap(), None), "Hello");
+        assert!(i18n.has_message(&en, "hello".try_into().unwrap()));
+        assert_eq!(i18n.message_ids(&en), None, "sources aren't loaded");
+    }
+}
@@ -1,10 +1,23 @@
 #![doc = include_str!("../README.md")]
 
+mod args;
 pub mod builders;
+mod cache;
+#[cfg(feature = "config")]
+#[cfg_attr(docsrs, doc(cfg(feature = "config")))]
+pub mod config;
+pub mod coverage;
 pub mod error;
 pub mod fkey;
+pub mod functions;
+pub mod html;
+mod introspect;
 pub mod loaders;
+mod localizer;
 pub mod machine;
+pub mod reload;
+pub mod types;
+pub mod validation;
 
 #[cfg(feature = "actix-web4")]
 mod actix;
@@ -14,9 +27,11 @@ pub use fluent_bundle::{FluentArgs, FluentError, FluentResource, FluentValue};
 pub use fluent_langneg::NegotiationStrategy;
 pub use unic_langid::LanguageIdentifier;
 
+pub use cache::NegotiationCacheStats;
 pub use error::Error;
 pub use fkey::Fkey;
-pub use machine::{FluentMachine, FluentMachineLoader, MachineBundle};
+pub use localizer::Localizer;
+pub use machine::{FluentMachine, FluentMachineLoader, MachineBundle, MissingKeyPolicy};
 
 /// A helper macro to simplify creation of FluentArgs.
 ///
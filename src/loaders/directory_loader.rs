@@ -2,8 +2,9 @@ use ahash::RandomState;
 use std::{collections::HashMap, fs, io, path::Path, sync::Arc};
 
 use crate::{
-    machine::MachineBundles, Error, FluentMachineLoader, FluentResource, LanguageIdentifier,
-    MachineBundle,
+    builders::{parent_locales, FluentMachineBuilder},
+    machine::{MachineBundles, MachineResources},
+    Error, FluentMachineLoader, FluentResource, LanguageIdentifier,
 };
 
 #[derive(Debug)]
@@ -30,8 +31,8 @@ struct FluentSource {
 ///         > football = { -football-term } is the biggest North American sport, with Super Bowl 112.3 million viewers.
 ///         > ```
 ///     - **`en-UK/`** -- Generates `en-UK`
-///         Will override parent language `terms` and `messages` to specific
-///         regional terms
+///       Will override parent language `terms` and `messages` to specific
+///       regional terms
 ///         - **`overrides.ftl`** will override the previous `en` `terms`
 ///         > ```text
 ///         > -soccer-term = Football
@@ -45,9 +46,13 @@ struct FluentSource {
 ///         - `..`
 ///     - **`pt-BR/`**
 ///         - `..`
+///
 /// Generates `en` as base language, `en-US` and `en-UK` by overriding the
 /// base language.
 ///
+/// Script and variant directories inherit in several levels, `zh-Hant-TW/` overrides
+/// `zh-Hant/`, overriding `zh/`, and `ca-ES-valencia/` overrides `ca-ES/`, overriding `ca/`.
+///
 /// ### Warning
 ///
 /// While loading does not fail unless a file error, parsing can generate errors
@@ -66,7 +71,8 @@ impl<'a> DirectoryLoader<'a> {
 //     1. sorts by name
 //     2. if is file and ends with `ftl`:
 //         a. extract `tag` from language region
-//         b. store if `tag` exist else create new `tag` copying all from parent (without region)
+//         b. store if `tag` exist else create new `tag` copying all from the nearest parent,
+//            without variants, region or script, or global
 //     3. at the end repeat 1 for all sub directories
 #[inline]
 fn load_directory(
@@ -90,7 +96,7 @@ fn load_directory(
             dirs.push(path);
         } else if path
             .extension()
-            .map_or(false, |ext| ext.to_str() == Some("ftl"))
+            .is_some_and(|ext| ext.to_str() == Some("ftl"))
         {
             let lang: Option<LanguageIdentifier> = path
                 .iter()
@@ -106,19 +112,16 @@ fn load_directory(
             if files.contains_key(&lang) {
                 files.entry(lang).and_modify(|v| v.push(file));
             } else {
-                let pre_key = match lang {
-                    Some(ref l) if l.region.is_some() => Some(LanguageIdentifier::from_parts(
-                        l.language,
-                        l.script,
-                        None,
-                        &[],
-                    )),
-                    _ => None,
-                };
+                // nearest loaded parent, already including its own parents
+                let pre_key = lang.as_ref().and_then(|l| {
+                    parent_locales(l)
+                        .into_iter()
+                        .find(|p| files.contains_key(&Some(p.clone())))
+                });
                 let lang_prev: Vec<Arc<FluentSource>> = files
                     .get(&pre_key)
                     .map(|s| s.iter().map(Arc::clone).collect())
-                    .unwrap_or_else(Vec::new);
+                    .unwrap_or_default();
 
                 files
                     .entry(lang)
@@ -141,17 +144,28 @@ fn load_directory(
 
 impl<'a> FluentMachineLoader for DirectoryLoader<'a> {
     fn load(&self) -> Result<MachineBundles, Error> {
+        Ok(FluentMachineBuilder::from_resources(self.resources()?).bundles)
+    }
+
+    fn load_resources(&self) -> Result<Option<MachineResources>, Error> {
+        self.resources().map(Some)
+    }
+}
+
+impl<'a> DirectoryLoader<'a> {
+    /// Reads the resources of each locale, in the order they are added to the bundle.
+    fn resources(&self) -> Result<MachineResources, Error> {
         log::info!(target: "DirectoryLoader", "Loading fluent translations");
         let mut read_files = HashMap::new();
 
         load_directory(self.0, self.0.iter().count(), &mut read_files)?;
 
-        let mut out: MachineBundles =
+        let mut out: MachineResources =
             HashMap::with_capacity_and_hasher(read_files.len(), RandomState::new());
         let mut errors_map = HashMap::new();
         for (k, xs) in read_files.iter() {
             if let Some(k) = k {
-                let mut bundle: MachineBundle = MachineBundle::new_concurrent(vec![k.clone()]);
+                let mut resources = Vec::with_capacity(xs.len());
                 for s in xs {
                     let resource = match FluentResource::try_new(s.ftl.to_string()) {
                         Ok(r) => r,
@@ -162,9 +176,9 @@ impl<'a> FluentMachineLoader for DirectoryLoader<'a> {
                             r
                         }
                     };
-                    bundle.add_resource_overriding(Arc::new(resource));
+                    resources.push(Arc::new(resource));
                 }
-                out.insert(k.clone(), bundle);
+                out.insert(k.clone(), resources);
             }
         }
         if !errors_map.is_empty() {
@@ -264,6 +278,55 @@ mod tests {
         );
     }
 
+    #[test]
+    fn load_script_and_variant_order_respected() {
+        let mut read_files = HashMap::new();
+        let path = Path::new("./examples/inheritance");
+        load_directory(path, path.iter().count(), &mut read_files).expect("failed to load files");
+        let sources = |locale: &str| {
+            read_files
+                .get(&locale.parse::<LanguageIdentifier>().ok())
+                .unwrap()
+                .iter()
+                .map(|f| f.source.as_str())
+                .collect::<Vec<&str>>()
+        };
+        assert_eq!(
+            sources("zh-Hant-TW"),
+            vec![
+                "./examples/inheritance/global.ftl",
+                "./examples/inheritance/zh/main.ftl",
+                "./examples/inheritance/zh-Hant/main.ftl",
+                "./examples/inheritance/zh-Hant-TW/main.ftl",
+            ]
+        );
+        assert_eq!(
+            sources("ca-ES-valencia"),
+            vec![
+                "./examples/inheritance/global.ftl",
+                "./examples/inheritance/ca/main.ftl",
+                "./examples/inheritance/ca-ES/main.ftl",
+                "./examples/inheritance/ca-ES-valencia/main.ftl",
+            ]
+        );
+
+        let i18n = FluentMachine::build_loader(DirectoryLoader::new("examples/inheritance/"))
+            .unwrap()
+            .set_fallback_locale("zh")
+            .expect("failed to parse locale")
+            .finish()
+            .expect("failed to create FluentMachine");
+        let tw: LanguageIdentifier = "zh-Hant-TW".parse().unwrap();
+        let valencia: LanguageIdentifier = "ca-ES-valencia".parse().unwrap();
+        let t = |l: &LanguageIdentifier, key: &str| i18n.t(&[l], key.try_into().unwrap(), None);
+        assert_eq!(t(&tw, "brand"), "Example");
+        assert_eq!(t(&tw, "language"), "中文");
+        assert_eq!(t(&tw, "script"), "繁體");
+        assert_eq!(t(&tw, "region"), "台灣");
+        assert_eq!(t(&valencia, "region"), "Espanya");
+        assert_eq!(t(&valencia, "variant"), "Valencià");
+    }
+
     #[test]
     fn i18n_loader_translate_us() {
         let i18n = FluentMachine::build_loader(DirectoryLoader::new("examples/locales/"))
@@ -2,7 +2,7 @@ use std::{fs, io, path::Path, sync::Arc};
 
 use crate::{
     builders::{FluentMachineInheritanceBuilder, InheritanceSyntaxErrorHandling},
-    error::FluentResourceError,
+    error::{Diagnostic, FluentResourceError},
     Error, FluentResource, LanguageIdentifier,
 };
 
@@ -13,8 +13,16 @@ struct FluentSource {
     locale: Option<LanguageIdentifier>,
 }
 
+/// Locale metadata file name, in the locale directory
+const META_FILE: &str = "locale.meta";
+
 #[inline]
-fn load_directory(p: &Path, skip: usize, files: &mut Vec<FluentSource>) -> Result<(), Error> {
+fn load_directory(
+    p: &Path,
+    skip: usize,
+    files: &mut Vec<FluentSource>,
+    metas: &mut Vec<FluentSource>,
+) -> Result<(), Error> {
     if p.is_file() {
         return Ok(());
     }
@@ -29,9 +37,22 @@ fn load_directory(p: &Path, skip: usize, files: &mut Vec<FluentSource>) -> Resul
     for path in entries {
         if path.is_dir() {
             dirs.push(path);
+        } else if path
+            .file_name()
+            .is_some_and(|name| name.to_str() == Some(META_FILE))
+        {
+            metas.push(FluentSource {
+                source: path.to_string_lossy().to_string(),
+                ftl: fs::read_to_string(&path)?,
+                locale: path
+                    .iter()
+                    .nth(skip)
+                    .and_then(|f| f.to_str())
+                    .and_then(|f| f.parse().ok()),
+            });
         } else if path
             .extension()
-            .map_or(false, |ext| ext.to_str() == Some("ftl"))
+            .is_some_and(|ext| ext.to_str() == Some("ftl"))
         {
             let locale: Option<LanguageIdentifier> = path
                 .iter()
@@ -47,7 +68,7 @@ fn load_directory(p: &Path, skip: usize, files: &mut Vec<FluentSource>) -> Resul
         }
     }
     for d in dirs.iter() {
-        load_directory(d, skip, files)?;
+        load_directory(d, skip, files, metas)?;
     }
     Ok(())
 }
@@ -72,8 +93,8 @@ impl FluentMachineInheritanceBuilder {
     ///         > football = { -football-term } is the biggest North American sport, with Super Bowl 112.3 million viewers.
     ///         > ```
     ///     - **`en-UK/`** -- Generates `en-UK`
-    ///         Will override parent language `terms` and `messages` to specific
-    ///         regional terms
+    ///       Will override parent language `terms` and `messages` to specific
+    ///       regional terms
     ///         - **`overrides.ftl`** will override the previous `en` `terms`
     ///         > ```text
     ///         > -soccer-term = Football
@@ -87,9 +108,24 @@ impl FluentMachineInheritanceBuilder {
     ///         - `..`
     ///     - **`pt-BR/`**
     ///         - `..`
+    ///
     /// Generates `en` as base language, `en-US` and `en-UK` by overriding the
     /// base language.
     ///
+    /// A locale directory can declare its parent, check
+    /// [`set_parent`](FluentMachineInheritanceBuilder::set_parent), in a `locale.meta` file:
+    /// > ```text
+    /// > # pt-AO/locale.meta
+    /// > parent = pt-PT
+    /// > ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if a file can't be read.
+    ///
+    /// Returns [`Error::LocaleMeta`] with the file path and line if a `locale.meta` line is
+    /// invalid.
+    ///
     /// # Example
     /// ```rust
     /// use fi18n::{
@@ -165,14 +201,18 @@ impl FluentMachineInheritanceBuilder {
     ///     "Futebol é o maior desporto do mundo, com os 380 milhões de telespectadores na final da Champions League."
     /// );
     /// ```
-
     pub fn load_locales(mut self, path: &str) -> Result<FluentMachineInheritanceBuilder, Error> {
         let p = Path::new(path);
 
         log::info!(target: "DirectoryLoader", "Loading fluent translations");
         let mut read_files = Vec::new();
+        let mut metas = Vec::new();
+
+        load_directory(p, p.iter().count(), &mut read_files, &mut metas)?;
 
-        load_directory(p, p.iter().count(), &mut read_files)?;
+        for meta in metas.iter() {
+            self = self.load_meta(meta)?;
+        }
 
         for FluentSource {
             ftl,
@@ -182,16 +222,25 @@ impl FluentMachineInheritanceBuilder {
         {
             let rs = match FluentResource::try_new(ftl.to_string()) {
                 Ok(rs) => Arc::new(rs),
-                Err((rs, exs)) => {
-                    let err = FluentResourceError::new(&ftl, Some(&source), exs);
-                    match self.mode {
-                        InheritanceSyntaxErrorHandling::AtAddSource => return Err(err.into()),
-                        InheritanceSyntaxErrorHandling::AtBuild => {
-                            self.errors.push(err);
-                            Arc::new(rs)
-                        }
+                Err((rs, exs)) => match self.mode {
+                    InheritanceSyntaxErrorHandling::AtAddSource => {
+                        return Err(FluentResourceError::new(&ftl, Some(&source), exs).into())
+                    }
+                    InheritanceSyntaxErrorHandling::AtBuild => {
+                        self.errors
+                            .push(FluentResourceError::new(&ftl, Some(&source), exs));
+                        Arc::new(rs)
+                    }
+                    InheritanceSyntaxErrorHandling::Recover => {
+                        self.diagnostics.extend(Diagnostic::from_errors(
+                            locale.as_ref(),
+                            &ftl,
+                            Some(&source),
+                            &exs,
+                        ));
+                        Arc::new(rs)
                     }
-                }
+                },
             };
 
             self.sources
@@ -203,6 +252,33 @@ impl FluentMachineInheritanceBuilder {
     }
 }
 
+impl FluentMachineInheritanceBuilder {
+    /// Applies the `key = value` lines of a locale metadata file, `#` starts a comment.
+    fn load_meta(mut self, meta: &FluentSource) -> Result<Self, Error> {
+        let invalid = |line: usize, message: &str| Error::LocaleMeta {
+            path: meta.source.clone(),
+            line,
+            message: message.to_string(),
+        };
+        let locale = match &meta.locale {
+            Some(locale) => locale.to_string(),
+            None => return Err(invalid(1, "expected in a locale directory")),
+        };
+        for (n, line) in meta.ftl.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            match line.split_once('=').map(|(k, v)| (k.trim(), v.trim())) {
+                Some(("parent", parent)) => self = self.set_parent(&locale, parent)?,
+                Some((key, _)) => return Err(invalid(n + 1, &format!("unknown key `{key}`"))),
+                None => return Err(invalid(n + 1, "expected `key = value`")),
+            }
+        }
+        Ok(self)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -379,4 +455,46 @@ mod tests {
             "Entre o seu nome de usuário"
         );
     }
+
+    #[test]
+    fn i18n_loader_declared_parents() {
+        let i18n = FluentMachine::build_with_inheritance(InheritanceSyntaxErrorHandling::AtBuild)
+            .load_locales("examples/parents/")
+            .unwrap()
+            .build_inheritance()
+            .unwrap()
+            .set_fallback_locale("pt")
+            .expect("failed to parse locale")
+            .finish()
+            .expect("failed to create FluentMachine");
+        let mz: LanguageIdentifier = "pt-MZ".parse().unwrap();
+        assert_eq!(
+            i18n.t(&[&mz], "bus".try_into().unwrap(), None),
+            "Machimbombo"
+        );
+        assert_eq!(i18n.t(&[&mz], "team".try_into().unwrap(), None), "Equipa");
+        assert_eq!(i18n.t(&[&mz], "hello".try_into().unwrap(), None), "Olá");
+    }
+
+    #[test]
+    fn invalid_meta_line() {
+        let builder = FluentMachineInheritanceBuilder::new(InheritanceSyntaxErrorHandling::AtBuild);
+        let meta = FluentSource {
+            source: "locales/pt-AO/locale.meta".into(),
+            ftl: "# comment\n\nparent: pt-PT".into(),
+            locale: "pt-AO".parse().ok(),
+        };
+        let err = builder.load_meta(&meta).err().expect("Should fail");
+        assert!(
+            matches!(
+                &err,
+                Error::LocaleMeta { path, line: 3, .. } if path == "locales/pt-AO/locale.meta"
+            ),
+            "{err:?}"
+        );
+        assert_eq!(
+            err.to_string(),
+            "Invalid locale metadata `locales/pt-AO/locale.meta` line 3: expected `key = value`"
+        );
+    }
 }
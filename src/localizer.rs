@@ -0,0 +1,211 @@
+/*!
+Translation handle bound to the resolved locales
+*/
+
+use std::{borrow::Cow, fmt};
+
+use crate::{
+    args::{DefaultArgs, MergedArgs},
+    error::TranslateError,
+    html::SafeHtml,
+    Fkey, FluentArgs, FluentMachine, LanguageIdentifier,
+};
+
+/// Translates with [`FluentMachine`] to the resolved locales, returned by
+/// [`FluentMachine::localize_t`].
+///
+/// Cheap to clone and can be shared between threads.
+///
+/// # Example
+/// ```rust
+/// use fi18n::{f_args, FluentMachine, LanguageIdentifier};
+///
+/// let i18n = FluentMachine::build()
+///     .add_resource("en", r#"hello = Hello { $name }"#).expect("Should add en")
+///     .add_resource("pt", r#"hello = Olá { $name }"#).expect("Should add pt")
+///     .finish().expect("Should finish");
+///
+/// let pt = i18n.localize_t("pt-PT, en;0.5");
+/// assert_eq!(pt.primary_locale(), Some(&"pt".parse::<LanguageIdentifier>().unwrap()));
+/// assert!(pt.has("hello".try_into().unwrap()));
+/// assert_eq!(
+///     pt.t("hello".try_into().unwrap(), Some(&f_args!["name" => "Ana"])),
+///     "Olá \u{2068}Ana\u{2069}"
+/// );
+/// ```
+#[derive(Clone)]
+pub struct Localizer<'a> {
+    machine: &'a FluentMachine,
+    locales: Vec<&'a LanguageIdentifier>,
+    default_args: Option<DefaultArgs>,
+}
+
+impl<'a> Localizer<'a> {
+    pub(crate) fn new(machine: &'a FluentMachine, locales: Vec<&'a LanguageIdentifier>) -> Self {
+        Self {
+            machine,
+            locales,
+            default_args: None,
+        }
+    }
+
+    /// Set arguments passed to every translation, merged under the caller arguments and over
+    /// [`FluentMachineBuilder::set_default_args`](crate::builders::FluentMachineBuilder::set_default_args).
+    ///
+    /// # Example
+    /// ```rust
+    /// use fi18n::{f_args, FluentMachine};
+    ///
+    /// let i18n = FluentMachine::build()
+    ///     .add_resource("en", r#"signed = { $user } signed in to { $appName }"#)
+    ///     .expect("Should add en")
+    ///     .set_default_args(&f_args!["appName" => "Acme", "user" => "guest"])
+    ///     .finish()
+    ///     .expect("Should finish");
+    ///
+    /// let en = i18n.localize_t("en").with_default_args(&f_args!["user" => "Ana"]);
+    /// assert_eq!(
+    ///     en.t("signed".try_into().unwrap(), None),
+    ///     "\u{2068}Ana\u{2069} signed in to \u{2068}Acme\u{2069}"
+    /// );
+    /// ```
+    pub fn with_default_args(mut self, args: &FluentArgs) -> Self {
+        self.default_args = Some(DefaultArgs::new(args));
+        self
+    }
+
+    /// Returns the default arguments with `args`, borrowing `args` without default arguments.
+    #[inline]
+    fn merge_default_args<'b>(&'b self, args: Option<&'b FluentArgs<'b>>) -> MergedArgs<'b> {
+        match &self.default_args {
+            Some(defaults) => defaults.merge(args),
+            None => MergedArgs::Borrowed(args),
+        }
+    }
+
+    /// Check [`FluentMachine::t`].
+    ///
+    /// # Panics
+    ///
+    /// On a missing key, according to [`MissingKeyPolicy`](crate::MissingKeyPolicy).
+    #[inline]
+    pub fn t(&self, path: Fkey, args: Option<&FluentArgs>) -> String {
+        let merged = self.merge_default_args(args);
+        self.machine.t(&self.locales, path, merged.get())
+    }
+
+    /// Check [`FluentMachine::try_t`].
+    #[inline]
+    pub fn try_t(&self, path: Fkey, args: Option<&FluentArgs>) -> Result<String, TranslateError> {
+        let merged = self.merge_default_args(args);
+        self.machine.try_t(&self.locales, path, merged.get())
+    }
+
+    /// Check [`FluentMachine::t_cow`].
+    ///
+    /// # Panics
+    ///
+    /// On a missing key, according to [`MissingKeyPolicy`](crate::MissingKeyPolicy).
+    #[inline]
+    pub fn t_cow(&self, path: Fkey, args: Option<&FluentArgs>) -> Cow<'a, str> {
+        let merged = self.merge_default_args(args);
+        self.machine.t_cow(&self.locales, path, merged.get())
+    }
+
+    /// Check [`FluentMachine::write_t`].
+    ///
+    /// # Panics
+    ///
+    /// On a missing key, according to [`MissingKeyPolicy`](crate::MissingKeyPolicy).
+    #[inline]
+    pub fn write_t<W: fmt::Write>(
+        &self,
+        w: &mut W,
+        path: Fkey,
+        args: Option<&FluentArgs>,
+    ) -> fmt::Result {
+        let merged = self.merge_default_args(args);
+        self.machine.write_t(w, &self.locales, path, merged.get())
+    }
+
+    /// Check [`FluentMachine::t_html`].
+    ///
+    /// # Panics
+    ///
+    /// On a missing key, according to [`MissingKeyPolicy`](crate::MissingKeyPolicy).
+    #[inline]
+    pub fn t_html(&self, path: Fkey, args: Option<&FluentArgs>) -> SafeHtml {
+        let merged = self.merge_default_args(args);
+        self.machine.t_html(&self.locales, path, merged.get())
+    }
+
+    /// Returns `true` if any of the locales has the message, or attribute, value.
+    #[inline]
+    pub fn has(&self, path: Fkey) -> bool {
+        self.machine.resolve(&self.locales, path).is_ok()
+    }
+
+    /// Returns the resolved locales, by preference.
+    #[inline]
+    pub fn locales(&self) -> &[&'a LanguageIdentifier] {
+        &self.locales
+    }
+
+    /// Returns the preferred locale.
+    #[inline]
+    pub fn primary_locale(&self) -> Option<&'a LanguageIdentifier> {
+        self.locales.first().copied()
+    }
+
+    /// Returns the [`FluentMachine`].
+    #[inline]
+    pub fn machine(&self) -> &'a FluentMachine {
+        self.machine
+    }
+}
+
+impl<'a> fmt::Debug for Localizer<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Localizer")
+            .field("locales", &self.locales)
+            .field("default_args", &self.default_args)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Localizer;
+    use crate::{FluentMachine, LanguageIdentifier};
+
+    fn assert_send_sync<T: Clone + Send + Sync>(_: &T) {}
+
+    #[test]
+    fn localizer_is_shareable() {
+        let i18n = FluentMachine::build()
+            .add_resource("en", r#"region = International"#)
+            .expect("Should add en")
+            .add_resource("en-US", r#"region = United States"#)
+            .expect("Should add en-US")
+            .finish()
+            .unwrap();
+        let us: Localizer = i18n.localize_t("en-US");
+        assert_send_sync(&us);
+        assert_eq!(
+            us.locales(),
+            &[
+                &"en-US".parse::<LanguageIdentifier>().unwrap(),
+                &"en".parse::<LanguageIdentifier>().unwrap()
+            ]
+        );
+        assert!(!us.has("missing".try_into().unwrap()));
+
+        let shared = us.clone();
+        let region = std::thread::scope(|s| {
+            s.spawn(move || shared.t("region".try_into().unwrap(), None))
+                .join()
+                .unwrap()
+        });
+        assert_eq!(region, "United States");
+    }
+}
@@ -4,16 +4,20 @@ builders to simplify the configuration and generation of [`FluentMachine`](crate
 
 */
 use ahash::RandomState;
-use fluent_bundle::{FluentArgs, FluentResource};
+use fluent_bundle::{FluentArgs, FluentError, FluentResource, FluentValue};
 use fluent_langneg::{negotiate_languages, parse_accepted_languages, NegotiationStrategy};
-use std::{collections::HashMap, sync::Arc};
+use fluent_syntax::ast::Pattern;
+use std::{borrow::Cow, collections::HashMap, fmt, sync::Arc};
 use unic_langid::LanguageIdentifier;
 
 use crate::{
+    args::{DefaultArgs, MergedArgs},
     builders::{
         FluentMachineBuilder, FluentMachineInheritanceBuilder, InheritanceSyntaxErrorHandling,
     },
-    Error, Fkey,
+    cache::{NegotiationCache, NegotiationCacheStats},
+    error::{Diagnostic, TranslateError},
+    Error, Fkey, Localizer,
 };
 
 /// Concurrent `FluentBlunde`
@@ -22,22 +26,95 @@ pub type MachineBundle = fluent_bundle::bundle::FluentBundle<
     intl_memoizer::concurrent::IntlLangMemoizer,
 >;
 
-/// Localized translation function to locale(s)
-pub type TranslateFn<'a> = Box<dyn Fn(Fkey<'a>, Option<&'_ FluentArgs>) -> String + 'a>;
+/// Callback used by [`MissingKeyPolicy::Callback`], receives the key, arguments and locale chain
+pub type MissingKeyFn =
+    Arc<dyn Fn(Fkey, Option<&FluentArgs>, &[&LanguageIdentifier]) -> String + Send + Sync>;
+
+/// What [`FluentMachine::t`] returns when no negotiated locale has the key.
+///
+/// Set with [`FluentMachineBuilder::set_missing_key_policy`].
+#[derive(Clone, Default)]
+pub enum MissingKeyPolicy {
+    /// Returns the key, `login.not-found`
+    #[default]
+    Key,
+    /// Returns an empty string
+    Empty,
+    /// Returns a visible marker, `⟦login.not-found⟧`
+    Marker,
+    /// Panics in debug builds, returns the key in release builds
+    PanicInDebug,
+    /// Returns the result of the callback
+    Callback(MissingKeyFn),
+}
+
+impl fmt::Debug for MissingKeyPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Key => write!(f, "Key"),
+            Self::Empty => write!(f, "Empty"),
+            Self::Marker => write!(f, "Marker"),
+            Self::PanicInDebug => write!(f, "PanicInDebug"),
+            Self::Callback(_) => write!(f, "Callback"),
+        }
+    }
+}
+
+/// Function callable from FTL, shared by the bundles, check
+/// [`FluentMachineBuilder::add_shared_function`](crate::builders::FluentMachineBuilder::add_shared_function)
+pub type FunctionFn =
+    Arc<dyn for<'a> Fn(&[FluentValue<'a>], &FluentArgs) -> FluentValue<'a> + Send + Sync>;
+
+/// Receives formatting errors with the key and the locale that formatted it
+pub type FormatErrorFn = Arc<dyn Fn(Fkey, &LanguageIdentifier, &[FluentError]) + Send + Sync>;
+
+/// Translation with the formatting errors, returned by [`FluentMachine::t_with_errors`]
+#[derive(Debug)]
+pub struct Translation {
+    /// Formatted value, or the [`MissingKeyPolicy`] value
+    pub value: String,
+    /// Collected formatting errors
+    pub errors: Vec<FluentError>,
+    /// Locale that formatted the value, `None` if the key is missing
+    pub locale: Option<LanguageIdentifier>,
+}
 
 pub(crate) type MachineBundles = HashMap<LanguageIdentifier, MachineBundle, RandomState>;
 
+/// Resources of each locale, in the order they are added to the bundle
+pub(crate) type MachineResources =
+    HashMap<LanguageIdentifier, Vec<Arc<FluentResource>>, RandomState>;
+
 pub trait FluentMachineLoader {
     fn load(&self) -> Result<MachineBundles, Error>;
+
+    /// Returns the resources of each locale, later resources override the previous entries,
+    /// used by [`FluentMachine::build_loader`] instead of [`FluentMachineLoader::load`] to keep
+    /// the message sources for introspection and validation, check [`FluentMachine::message_ids`].
+    ///
+    /// Default `None`, the bundles of [`FluentMachineLoader::load`] are used without sources.
+    fn load_resources(&self) -> Result<Option<MachineResources>, Error> {
+        Ok(None)
+    }
 }
 
 /// Simple Fluent API
 pub struct FluentMachine {
     pub(crate) bundles: MachineBundles,
+    pub(crate) resources: MachineResources,
     // stored ordered locales
     pub(crate) available: Vec<LanguageIdentifier>,
     pub(crate) fallback: LanguageIdentifier,
     pub(crate) strategy: NegotiationStrategy,
+    pub(crate) missing_key: MissingKeyPolicy,
+    pub(crate) negotiation_cache: Option<NegotiationCache>,
+    pub(crate) format_error_handler: Option<FormatErrorFn>,
+    pub(crate) default_args: Option<DefaultArgs>,
+    /// requested locale to available locale
+    pub(crate) aliases: HashMap<LanguageIdentifier, LanguageIdentifier, RandomState>,
+    /// locales searched after the negotiated locale
+    pub(crate) fallback_chains: HashMap<LanguageIdentifier, Vec<LanguageIdentifier>, RandomState>,
+    pub(crate) diagnostics: Vec<Diagnostic>,
     #[cfg(feature = "actix-web4")]
     pub(crate) fallback_string: String,
     #[cfg(feature = "actix-web4")]
@@ -50,9 +127,12 @@ impl FluentMachine {
     where
         T: FluentMachineLoader,
     {
-        Ok(FluentMachineBuilder {
-            bundles: loader.load()?,
-            ..Default::default()
+        Ok(match loader.load_resources()? {
+            Some(resources) => FluentMachineBuilder::from_resources(resources),
+            None => FluentMachineBuilder {
+                bundles: loader.load()?,
+                ..Default::default()
+            },
         })
     }
 
@@ -69,7 +149,20 @@ impl FluentMachine {
     }
 
     /// Returns translation according to the first message found, respecting the
-    /// negotiated languages order with available.
+    /// negotiated languages order with available, followed by their fallback chains and the
+    /// fallback locale, check [`FluentMachineBuilder::set_fallback_chain`].
+    ///
+    /// On failure logs and returns the partially formatted value, in case of formatting
+    /// errors, or the value of [`MissingKeyPolicy`], by default the `path`.
+    ///
+    /// `args` are merged over the default arguments, check
+    /// [`FluentMachineBuilder::set_default_args`].
+    ///
+    /// # Panics
+    ///
+    /// Panics in debug builds if no locale has the message, or attribute, value and the
+    /// policy is [`MissingKeyPolicy::PanicInDebug`], a [`MissingKeyPolicy::Callback`] may
+    /// panic too.
     #[inline]
     pub fn t(
         &self,
@@ -77,46 +170,412 @@ impl FluentMachine {
         path: Fkey,
         args: Option<&FluentArgs>,
     ) -> String {
-        for locale in negotiated.iter() {
-            let bundle = self.bundles.get(locale).unwrap();
+        let merged = self.merge_default_args(args);
+        let args = merged.get();
+        match self.format(negotiated, path, args) {
+            Ok(t) => t,
+            Err(TranslateError::Format { value, .. }) => value,
+            Err(_) => {
+                let mut out = String::new();
+                // writing to a `String` never fails
+                let _ = self.write_missing(&mut out, negotiated, path, args);
+                out
+            }
+        }
+    }
+
+    /// Same as [`FluentMachine::t`], but borrows from the bundle when the message
+    /// pattern is a plain literal.
+    ///
+    /// Arguments are merged with the default arguments as in [`FluentMachine::write_t`].
+    ///
+    /// # Panics
+    ///
+    /// On a missing key, according to [`MissingKeyPolicy`], check [`FluentMachine::t`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use fi18n::{f_args, FluentMachine};
+    /// use std::borrow::Cow;
+    ///
+    /// let i18n = FluentMachine::build()
+    ///     .add_resource("en", r#"
+    /// title = Welcome
+    /// hello = Hello { $name }"#).expect("Should add en")
+    ///     .finish().expect("Should finish");
+    /// let en = i18n.negotiate_languages("en");
+    ///
+    /// assert!(matches!(i18n.t_cow(&en, "title".try_into().unwrap(), None), Cow::Borrowed("Welcome")));
+    /// assert!(matches!(
+    ///     i18n.t_cow(&en, "hello".try_into().unwrap(), Some(&f_args!["name" => "Joe"])),
+    ///     Cow::Owned(_)
+    /// ));
+    /// ```
+    #[inline]
+    pub fn t_cow<'m>(
+        &'m self,
+        negotiated: &[&LanguageIdentifier],
+        path: Fkey,
+        args: Option<&FluentArgs>,
+    ) -> Cow<'m, str> {
+        let merged = self.merge_default_args(args);
+        let args = merged.get();
+        match self.resolve(negotiated, path) {
+            Ok((locale, bundle, pattern)) => {
+                let filled = Self::fill_defaulted(bundle, pattern, args);
+
+                let args = filled.as_ref().or(args);
+
+                let mut errors: Vec<_> = vec![];
+                let t = bundle.format_pattern(pattern, args, &mut errors);
+                self.report_format_errors(path, locale, &errors);
+                t
+            }
+            Err(_) => {
+                let mut out = String::new();
+                // writing to a `String` never fails
+                let _ = self.write_missing(&mut out, negotiated, path, args);
+                Cow::Owned(out)
+            }
+        }
+    }
+
+    /// Writes the translation into `w`, without intermediate allocations, with the same
+    /// resolution and [`MissingKeyPolicy`] as [`FluentMachine::t`].
+    ///
+    /// With [`FluentMachineBuilder::set_default_args`], the merged arguments are allocated.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`fmt::Error`] only if writing to `w` fails.
+    ///
+    /// # Panics
+    ///
+    /// On a missing key, according to [`MissingKeyPolicy`], check [`FluentMachine::t`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use fi18n::{f_args, FluentMachine};
+    ///
+    /// let i18n = FluentMachine::build()
+    ///     .add_resource("en", r#"hello = Hello { $name }"#).expect("Should add en")
+    ///     .finish().expect("Should finish");
+    /// let en = i18n.negotiate_languages("en");
+    ///
+    /// let mut out = String::from("<p>");
+    /// i18n.write_t(&mut out, &en, "hello".try_into().unwrap(), Some(&f_args!["name" => "Joe"]))
+    ///     .unwrap();
+    /// out.push_str("</p>");
+    /// assert_eq!(out, "<p>Hello \u{2068}Joe\u{2069}</p>");
+    /// ```
+    #[inline]
+    pub fn write_t<W: fmt::Write>(
+        &self,
+        w: &mut W,
+        negotiated: &[&LanguageIdentifier],
+        path: Fkey,
+        args: Option<&FluentArgs>,
+    ) -> fmt::Result {
+        let merged = self.merge_default_args(args);
+        let args = merged.get();
+        match self.resolve(negotiated, path) {
+            Ok((locale, bundle, pattern)) => {
+                let filled = Self::fill_defaulted(bundle, pattern, args);
+
+                let args = filled.as_ref().or(args);
 
-            let pattern = match path {
-                Fkey(msg_id, None) => bundle
-                    .get_message(msg_id)
-                    .map(|msg| msg.value().expect("Failed to parse pattern")),
-                Fkey(msg_id, Some(attr)) => bundle
-                    .get_message(msg_id)
-                    .map(|msg| msg.get_attribute(attr).unwrap().value()),
-            };
-            if let Some(pattern) = pattern {
                 let mut errors: Vec<_> = vec![];
-                let t = bundle
+                bundle.write_pattern(w, pattern, args, &mut errors)?;
+                self.report_format_errors(path, locale, &errors);
+                Ok(())
+            }
+            Err(_) => self.write_missing(w, negotiated, path, args),
+        }
+    }
+
+    /// Returns translation according to the first message found, respecting the
+    /// negotiated languages order with available.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TranslateError`] with the searched locales, when no locale has the message,
+    /// the attribute or the message value, or when formatting collects errors.
+    ///
+    /// # Example
+    /// ```rust
+    /// use fi18n::{FluentMachine, LanguageIdentifier, error::TranslateError};
+    ///
+    /// let i18n = FluentMachine::build()
+    ///     .add_resource("en", r#"
+    /// login = Login
+    ///     .username = Username
+    /// hello = Hello { $name }"#).expect("Should add en")
+    ///     .finish().expect("Should finish");
+    /// let en = i18n.negotiate_languages("en");
+    ///
+    /// assert_eq!(i18n.try_t(&en, "login.username".try_into().unwrap(), None).unwrap(), "Username");
+    /// assert!(matches!(
+    ///     i18n.try_t(&en, "login.password".try_into().unwrap(), None),
+    ///     Err(TranslateError::MissingAttribute { .. })
+    /// ));
+    /// assert!(matches!(
+    ///     i18n.try_t(&en, "hello".try_into().unwrap(), None),
+    ///     Err(TranslateError::Format { .. })
+    /// ));
+    /// ```
+    pub fn try_t(
+        &self,
+        negotiated: &[&LanguageIdentifier],
+        path: Fkey,
+        args: Option<&FluentArgs>,
+    ) -> Result<String, TranslateError> {
+        let merged = self.merge_default_args(args);
+        self.format(negotiated, path, merged.get())
+    }
+
+    /// Formats the message pattern, `args` already merged with the default arguments.
+    pub(crate) fn format(
+        &self,
+        negotiated: &[&LanguageIdentifier],
+        path: Fkey,
+        args: Option<&FluentArgs>,
+    ) -> Result<String, TranslateError> {
+        let (locale, bundle, pattern) = self.resolve(negotiated, path)?;
+        let filled = Self::fill_defaulted(bundle, pattern, args);
+
+        let args = filled.as_ref().or(args);
+
+        let mut errors: Vec<_> = vec![];
+        let t = bundle
+            .format_pattern(pattern, args, &mut errors)
+            .to_string();
+        self.report_format_errors(path, locale, &errors);
+        if errors.is_empty() {
+            return Ok(t);
+        }
+        Err(TranslateError::Format {
+            key: path.to_string(),
+            locale: locale.clone(),
+            locales: negotiated.iter().map(|l| (*l).clone()).collect(),
+            errors,
+            value: t,
+        })
+    }
+
+    /// Returns translation as [`FluentMachine::t`], with the formatting errors and the locale
+    /// that formatted it.
+    ///
+    /// # Panics
+    ///
+    /// On a missing key, according to [`MissingKeyPolicy`], check [`FluentMachine::t`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use fi18n::FluentMachine;
+    ///
+    /// let i18n = FluentMachine::build()
+    ///     .add_resource("en", r#"hello = Hello { $name }"#).expect("Should add en")
+    ///     .finish().expect("Should finish");
+    /// let en = i18n.negotiate_languages("en");
+    ///
+    /// let hello = i18n.t_with_errors(&en, "hello".try_into().unwrap(), None);
+    /// assert_eq!(hello.value, "Hello \u{2068}{$name}\u{2069}");
+    /// assert_eq!(hello.errors.len(), 1);
+    /// assert_eq!(hello.locale, Some("en".parse().unwrap()));
+    /// ```
+    pub fn t_with_errors(
+        &self,
+        negotiated: &[&LanguageIdentifier],
+        path: Fkey,
+        args: Option<&FluentArgs>,
+    ) -> Translation {
+        let merged = self.merge_default_args(args);
+        let args = merged.get();
+        match self.resolve(negotiated, path) {
+            Ok((locale, bundle, pattern)) => {
+                let filled = Self::fill_defaulted(bundle, pattern, args);
+
+                let args = filled.as_ref().or(args);
+
+                let mut errors: Vec<_> = vec![];
+                let value = bundle
                     .format_pattern(pattern, args, &mut errors)
                     .to_string();
-                if log::log_enabled!(log::Level::Debug) && !errors.is_empty() {
-                    log::debug!(
-                        "while formatting key {} the following errors where collected: \n {}",
-                        path,
-                        errors
+                self.report_format_errors(path, locale, &errors);
+                Translation {
+                    value,
+                    errors,
+                    locale: Some(locale.clone()),
+                }
+            }
+            Err(_) => {
+                let mut value = String::new();
+                // writing to a `String` never fails
+                let _ = self.write_missing(&mut value, negotiated, path, args);
+                Translation {
+                    value,
+                    errors: vec![],
+                    locale: None,
+                }
+            }
+        }
+    }
+
+    /// Returns the default arguments with `args`, borrowing `args` without default arguments.
+    pub(crate) fn merge_default_args<'a>(
+        &'a self,
+        args: Option<&'a FluentArgs<'a>>,
+    ) -> MergedArgs<'a> {
+        match &self.default_args {
+            Some(defaults) => defaults.merge(args),
+            None => MergedArgs::Borrowed(args),
+        }
+    }
+
+    /// Returns `args` with the missing `DEFAULT` variables of `pattern` set, check
+    /// [`crate::functions::default`].
+    #[cfg_attr(not(feature = "fn-default"), allow(unused_variables))]
+    pub(crate) fn fill_defaulted<'a>(
+        bundle: &'a MachineBundle,
+        pattern: &'a Pattern<&'a str>,
+        args: Option<&'a FluentArgs<'a>>,
+    ) -> Option<FluentArgs<'a>> {
+        #[cfg(feature = "fn-default")]
+        return crate::functions::fill_defaulted(bundle, pattern, args);
+        #[cfg(not(feature = "fn-default"))]
+        None
+    }
+
+    /// Logs and passes formatting errors to the [`FormatErrorFn`] handler.
+    pub(crate) fn report_format_errors(
+        &self,
+        path: Fkey,
+        locale: &LanguageIdentifier,
+        errors: &[FluentError],
+    ) {
+        if errors.is_empty() {
+            return;
+        }
+        if log::log_enabled!(log::Level::Debug) {
+            log::debug!(
+                "while formatting key {} the following errors where collected: \n {}",
+                path,
+                errors
+                    .iter()
+                    .map(|x| format!("{x:?}"))
+                    .collect::<Vec<String>>()
+                    .join("\n")
+            );
+        }
+        if let Some(handler) = &self.format_error_handler {
+            handler(path, locale, errors);
+        }
+    }
+
+    /// Finds the first locale with the message pattern, respecting the negotiated order,
+    /// followed by the fallback chains and the fallback locale.
+    pub(crate) fn resolve<'m>(
+        &'m self,
+        negotiated: &[&LanguageIdentifier],
+        path: Fkey,
+    ) -> Result<
+        (
+            &'m LanguageIdentifier,
+            &'m MachineBundle,
+            &'m Pattern<&'m str>,
+        ),
+        TranslateError,
+    > {
+        // message exists in a locale, but without the requested value or attribute
+        let mut found = false;
+        for locale in self.search_order(negotiated) {
+            let (locale, bundle) = match self.bundles.get_key_value(locale) {
+                Some(entry) => entry,
+                None => continue,
+            };
+            let msg = match bundle.get_message(path.0) {
+                Some(msg) => msg,
+                None => continue,
+            };
+            let pattern = match path {
+                Fkey(_, None) => msg.value(),
+                Fkey(_, Some(attr)) => msg.get_attribute(attr).map(|a| a.value()),
+            };
+            match pattern {
+                Some(pattern) => return Ok((locale, bundle, pattern)),
+                None => found = true,
+            }
+        }
+        let id = path.0.to_string();
+        let mut searched: Vec<LanguageIdentifier> = vec![];
+        for locale in self.search_order(negotiated) {
+            if !searched.contains(locale) {
+                searched.push(locale.clone());
+            }
+        }
+        let locales = searched.into_boxed_slice();
+        Err(match (found, path.1) {
+            (true, Some(attribute)) => TranslateError::MissingAttribute {
+                id,
+                attribute: attribute.to_string(),
+                locales,
+            },
+            (true, None) => TranslateError::MessageWithoutValue { id, locales },
+            (false, _) => TranslateError::MissingMessage { id, locales },
+        })
+    }
+
+    /// Locales searched for `negotiated`, followed by the fallback chain of each locale and
+    /// the fallback locale, chains are prepared at finish, without the fallback locale.
+    fn search_order<'l>(
+        &'l self,
+        negotiated: &'l [&'l LanguageIdentifier],
+    ) -> impl Iterator<Item = &'l LanguageIdentifier> {
+        let chains = negotiated
+            .iter()
+            .filter_map(|l| self.fallback_chains.get(*l))
+            .flatten();
+        negotiated
+            .iter()
+            .copied()
+            .chain(chains)
+            .chain(std::iter::once(&self.fallback))
+    }
+
+    /// Writes the missing `path` according to [`MissingKeyPolicy`].
+    pub(crate) fn write_missing<W: fmt::Write>(
+        &self,
+        w: &mut W,
+        negotiated: &[&LanguageIdentifier],
+        path: Fkey,
+        args: Option<&FluentArgs>,
+    ) -> fmt::Result {
+        log::debug!("missing key {}", path);
+        match &self.missing_key {
+            MissingKeyPolicy::Key => write!(w, "{path}"),
+            MissingKeyPolicy::Empty => Ok(()),
+            MissingKeyPolicy::Marker => write!(w, "⟦{path}⟧"),
+            MissingKeyPolicy::PanicInDebug => {
+                if cfg!(debug_assertions) {
+                    panic!(
+                        "missing key {path} in locales [{}]",
+                        negotiated
                             .iter()
-                            .map(|x| format!("{x:?}"))
+                            .map(|l| l.to_string())
                             .collect::<Vec<String>>()
-                            .join("\n")
+                            .join(", ")
                     );
                 }
-                return t;
+                write!(w, "{path}")
             }
+            MissingKeyPolicy::Callback(f) => w.write_str(&f(path, args, negotiated)),
         }
-        log::debug!("missing key {}", path);
-        path.to_string()
     }
 
-    /// Localized translation function
+    /// Returns [`Localizer`] to the negotiated `locales`, check [`FluentMachine::negotiate_languages`]
     #[inline]
-    pub fn localize_t(&self, locales: &str) -> TranslateFn<'_> {
-        let langs = self.negotiate_languages(locales);
-
-        Box::new(move |key, options| self.t(&langs, key, options))
+    pub fn localize_t(&self, locales: &str) -> Localizer<'_> {
+        Localizer::new(self, self.negotiate_languages(locales))
     }
 
     /// Parses `request` language preference filters and sorts with
@@ -187,16 +646,52 @@ impl FluentMachine {
     ///
     /// For more information how filter is done to check
     /// [negotiate](fluent_langneg::negotiate) from fluent_langeg
+    ///
+    /// When [`FluentMachineBuilder::set_negotiation_cache`] is set, results are cached by `requested`.
+    ///
+    /// Requested locales are replaced by their alias target, check
+    /// [`FluentMachineBuilder::add_alias`].
     #[inline]
     pub fn negotiate_languages(&self, requested: &str) -> Vec<&LanguageIdentifier> {
+        match &self.negotiation_cache {
+            Some(cache) => cache
+                .get_or_insert_with(requested, || {
+                    self.negotiate_uncached(requested)
+                        .into_iter()
+                        .filter_map(|l| self.available.iter().position(|a| a == l))
+                        .collect()
+                })
+                .iter()
+                .map(|&i| &self.available[i])
+                .collect(),
+            None => self.negotiate_uncached(requested),
+        }
+    }
+
+    #[inline]
+    fn negotiate_uncached(&self, requested: &str) -> Vec<&LanguageIdentifier> {
+        let mut requested = parse_accepted_languages(requested);
+        if !self.aliases.is_empty() {
+            for locale in requested.iter_mut() {
+                if let Some(target) = self.aliases.get(locale) {
+                    locale.clone_from(target);
+                }
+            }
+        }
         negotiate_languages(
-            &parse_accepted_languages(requested),
+            &requested,
             &self.available,
             Some(&self.fallback),
             self.strategy,
         )
     }
 
+    /// Returns [`FluentMachine::negotiate_languages`] cache hits and misses, if the cache is set.
+    #[inline]
+    pub fn get_negotiation_cache_stats(&self) -> Option<NegotiationCacheStats> {
+        self.negotiation_cache.as_ref().map(|c| c.stats())
+    }
+
     /// Returns supported locales.
     #[inline]
     pub fn get_supported_locales(&self) -> &[LanguageIdentifier] {
@@ -209,17 +704,43 @@ impl FluentMachine {
         &self.fallback
     }
 
+    /// Returns the available locale of `locale` alias, or `locale` without alias.
+    #[inline]
+    pub fn resolve_alias<'l>(&'l self, locale: &'l LanguageIdentifier) -> &'l LanguageIdentifier {
+        self.aliases.get(locale).unwrap_or(locale)
+    }
+
     /// Returns used [`NegotiationStrategy`].
     #[inline]
     pub fn get_strategy(&self) -> NegotiationStrategy {
         self.strategy
     }
+
+    /// Returns the entries dropped while building, check
+    /// [`InheritanceSyntaxErrorHandling::Recover`].
+    #[inline]
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    /// Returns used [`MissingKeyPolicy`].
+    #[inline]
+    pub fn get_missing_key_policy(&self) -> &MissingKeyPolicy {
+        &self.missing_key
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{FluentMachine, LanguageIdentifier, NegotiationStrategy};
-    use crate::{f_args, FluentValue};
+    use super::{
+        FluentMachine, FunctionFn, LanguageIdentifier, MissingKeyPolicy, NegotiationStrategy,
+        TranslateError,
+    };
+    use crate::{f_args, Error, FluentArgs, FluentValue};
+    use std::{
+        borrow::Cow,
+        sync::{Arc, Mutex},
+    };
 
     #[test]
     #[should_panic(expected = "LocaleUnavailable")]
@@ -256,7 +777,7 @@ other = is not ok
             .expect("Should add en")
             .add_resource_override("pt", r#"length = pt { STRLEN("12345") }"#)
             .expect("Should add pt")
-            .add_function("STRLEN", &|pos, _| match pos {
+            .add_function("STRLEN", |pos, _| match pos {
                 [FluentValue::String(s)] => s.len().into(),
                 _ => FluentValue::Error,
             })
@@ -275,6 +796,124 @@ other = is not ok
         );
     }
 
+    #[test]
+    fn adds_function_for_locale() {
+        let suffixes = Arc::new(vec!["st", "nd", "rd"]);
+        let i18n = FluentMachine::build()
+            .add_resource("en", "place = { ORDINAL(2) }")
+            .expect("Should add en")
+            .add_resource("pt", "place = { ORDINAL(2) }")
+            .expect("Should add pt")
+            .add_function_for_locale("pt", "ORDINAL", |pos, _| match pos {
+                [FluentValue::Number(n)] => format!("{}.º", n.value).into(),
+                _ => FluentValue::Error,
+            })
+            .expect("Should add pt function")
+            .add_function("ORDINAL", move |pos, _| match pos {
+                [FluentValue::Number(n)] => {
+                    let n = n.value as usize;
+                    let suffix = suffixes.get(n.wrapping_sub(1)).unwrap_or(&"th");
+                    format!("{n}{suffix}").into()
+                }
+                _ => FluentValue::Error,
+            })
+            .expect("Should add function")
+            .finish()
+            .expect("Should build with success");
+        let en: LanguageIdentifier = "en".parse().unwrap();
+        let pt: LanguageIdentifier = "pt".parse().unwrap();
+        assert_eq!(i18n.t(&[&en], "place".try_into().unwrap(), None), "2nd");
+        assert_eq!(i18n.t(&[&pt], "place".try_into().unwrap(), None), "2.º");
+    }
+
+    #[test]
+    fn adds_shared_function() {
+        let ordinal: FunctionFn = Arc::new(|pos, _| match pos {
+            [FluentValue::Number(n)] => format!("{}.º", n.value).into(),
+            _ => FluentValue::Error,
+        });
+        let i18n = FluentMachine::build()
+            .add_resource("en", "place = { ORDINAL(2) }")
+            .expect("Should add en")
+            .add_resource("pt", "place = { ORDINAL(2) }")
+            .expect("Should add pt")
+            .add_shared_function_for_locale("pt", "ORDINAL", Arc::clone(&ordinal))
+            .expect("Should add pt function")
+            .add_shared_function("ORDINAL", Arc::new(|_, _| "2nd".into()))
+            .expect("Should add function")
+            .finish()
+            .expect("Should build with success");
+        let en: LanguageIdentifier = "en".parse().unwrap();
+        let pt: LanguageIdentifier = "pt".parse().unwrap();
+        assert_eq!(i18n.t(&[&en], "place".try_into().unwrap(), None), "2nd");
+        assert_eq!(i18n.t(&[&pt], "place".try_into().unwrap(), None), "2.º");
+        assert_eq!(Arc::strong_count(&ordinal), 2);
+    }
+
+    #[test]
+    fn add_function_errors() {
+        fn f<'a>(_: &[FluentValue<'a>], _: &FluentArgs) -> FluentValue<'a> {
+            FluentValue::None
+        }
+        let err = FluentMachine::build()
+            .add_function("F", f)
+            .unwrap()
+            .add_function("F", f)
+            .unwrap_err();
+        assert!(matches!(err, Error::Overriding(names) if names == ["F"]));
+        let err = FluentMachine::build()
+            .add_resource("en", "a = a")
+            .unwrap()
+            .add_function_for_locale("pt", "F", f)
+            .unwrap()
+            .finish()
+            .err()
+            .expect("Should fail without pt");
+        assert!(matches!(err, Error::LocaleUnavailable(l) if l == "pt"));
+    }
+
+    #[test]
+    fn aliases_requested_locales() {
+        let i18n = FluentMachine::build()
+            .add_resource("en", "region = International")
+            .unwrap()
+            .add_resource("pt-PT", "region = Portugal")
+            .unwrap()
+            .add_resource("zh-Hans", "region = 国际")
+            .unwrap()
+            .add_alias("pt-AO", "pt-PT")
+            .unwrap()
+            .add_alias("zh-CN", "zh-Hans")
+            .unwrap()
+            .set_negotiation_cache(4)
+            .finish()
+            .unwrap();
+        let pt: LanguageIdentifier = "pt-PT".parse().unwrap();
+        let zh: LanguageIdentifier = "zh-Hans".parse().unwrap();
+        let en: LanguageIdentifier = "en".parse().unwrap();
+        assert_eq!(i18n.negotiate_languages("pt-AO"), vec![&pt, &en]);
+        assert_eq!(i18n.negotiate_languages("pt-AO"), vec![&pt, &en]);
+        assert_eq!(i18n.negotiate_languages("zh-CN, en;0.5"), vec![&zh, &en]);
+        assert_eq!(i18n.resolve_alias(&"zh-CN".parse().unwrap()), &zh);
+        assert_eq!(i18n.resolve_alias(&en), &en);
+
+        let err = FluentMachine::build()
+            .add_alias("en-GB", "en-UK")
+            .unwrap()
+            .add_alias("en-GB", "en")
+            .unwrap_err();
+        assert!(matches!(err, Error::Overriding(names) if names == ["en-GB"]));
+        let err = FluentMachine::build()
+            .add_resource("en", "a = a")
+            .unwrap()
+            .add_alias("en-GB", "en-UK")
+            .unwrap()
+            .finish()
+            .err()
+            .expect("Should fail without en-UK");
+        assert!(matches!(err, Error::LocaleUnavailable(l) if l == "en-UK"));
+    }
+
     #[test]
     fn supports_attribute() {
         let i18n = FluentMachine::build()
@@ -429,10 +1068,74 @@ region = United States
         );
         assert_eq!(
             i18n.t(&locales, "missing".try_into().unwrap(), None),
-            "missing"
+            "Missing on others",
+            "the fallback locale is searched last"
         );
     }
 
+    #[test]
+    fn t_follows_fallback_chains() {
+        let i18n = FluentMachine::build()
+            .add_resource("en", "region = International\nmissing = Missing on others")
+            .expect("Should add en")
+            .add_resource("en-US", "region = United States")
+            .expect("Should add en-US")
+            .add_resource("pt", "region = Internacional\ncolor = Cor")
+            .expect("Should add pt")
+            .add_resource("pt-PT", "region = Portugal\ncolor = Cor\nbus = Autocarro")
+            .expect("Should add pt-PT")
+            .add_resource("pt-BR", "region = Brasil\ncolor = Cor")
+            .expect("Should add pt-BR")
+            .set_strategy(NegotiationStrategy::Lookup)
+            .set_fallback_chain("en-US", &["en"])
+            .expect("Should set en-US chain")
+            .set_fallback_chain("pt-BR", &["pt", "pt-PT"])
+            .expect("Should set pt-BR chain")
+            .finish()
+            .unwrap();
+        let locales = i18n.negotiate_languages("de-AT;0.9, en-US;0.5");
+        assert_eq!(
+            locales,
+            vec![&"en-US".parse::<LanguageIdentifier>().unwrap()]
+        );
+        assert_eq!(
+            i18n.t(&locales, "missing".try_into().unwrap(), None),
+            "Missing on others"
+        );
+        assert!(matches!(
+            i18n.try_t(&locales, "unknown".try_into().unwrap(), None),
+            Err(TranslateError::MissingMessage { locales, .. }) if locales.len() == 2
+        ));
+        let br = i18n.localize_t("pt-BR");
+        assert_eq!(br.t("region".try_into().unwrap(), None), "Brasil");
+        assert_eq!(br.t("bus".try_into().unwrap(), None), "Autocarro");
+        assert_eq!(
+            br.t("missing".try_into().unwrap(), None),
+            "Missing on others",
+            "always ends at the fallback"
+        );
+        assert!(matches!(
+            br.try_t("unknown".try_into().unwrap(), None),
+            Err(TranslateError::MissingMessage { locales, .. }) if locales.len() == 4
+        ));
+
+        let err = FluentMachine::build()
+            .set_fallback_chain("pt-BR", &["pt"])
+            .unwrap()
+            .set_fallback_chain("pt-BR", &["pt-PT"])
+            .unwrap_err();
+        assert!(matches!(err, Error::Overriding(locales) if locales == ["pt-BR"]));
+        let err = FluentMachine::build()
+            .add_resource("en", "a = a")
+            .unwrap()
+            .set_fallback_chain("en", &["en-UK"])
+            .unwrap()
+            .finish()
+            .err()
+            .expect("Should fail without en-UK");
+        assert!(matches!(err, Error::LocaleUnavailable(l) if l == "en-UK"));
+    }
+
     #[test]
     fn localize_t_lookup() {
         let i18n = FluentMachine::build()
@@ -448,7 +1151,7 @@ region = United States
         let en = i18n.localize_t("en");
 
         assert_eq!(
-            en(
+            en.t(
                 "hello".try_into().unwrap(),
                 Some(&f_args![
                     "name" => "Joe",
@@ -457,4 +1160,253 @@ region = United States
             "Hello \u{2068}Joe\u{2069}."
         );
     }
+
+    #[test]
+    fn t_does_not_panic_on_missing_value_or_attribute() {
+        let i18n = FluentMachine::build()
+            .add_resource(
+                "en",
+                r#"
+login =
+    .placeholder = email@example.com
+"#,
+            )
+            .expect("Should add en")
+            .finish()
+            .expect("Should finish");
+        let en = i18n.negotiate_languages("en");
+        assert_eq!(i18n.t(&en, "login".try_into().unwrap(), None), "login");
+        assert_eq!(
+            i18n.t(&en, "login.missing".try_into().unwrap(), None),
+            "login.missing"
+        );
+    }
+
+    #[test]
+    fn try_t_returns_errors_with_searched_locales() {
+        let i18n = FluentMachine::build()
+            .add_resource(
+                "en",
+                r#"
+login =
+    .placeholder = email@example.com
+hello = Hello {$name}
+"#,
+            )
+            .expect("Should add en")
+            .add_resource("en-US", r#"region = United States"#)
+            .expect("Should add en-US")
+            .finish()
+            .expect("Should finish");
+        let locales = i18n.negotiate_languages("en-US");
+        let expected: Vec<LanguageIdentifier> =
+            vec!["en-US".parse().unwrap(), "en".parse().unwrap()];
+
+        match i18n.try_t(&locales, "missing".try_into().unwrap(), None) {
+            Err(TranslateError::MissingMessage { id, locales }) => {
+                assert_eq!(id, "missing");
+                assert_eq!(locales.to_vec(), expected);
+            }
+            other => panic!("Unexpected {other:?}"),
+        }
+        match i18n.try_t(&locales, "login".try_into().unwrap(), None) {
+            Err(TranslateError::MessageWithoutValue { id, locales }) => {
+                assert_eq!(id, "login");
+                assert_eq!(locales.to_vec(), expected);
+            }
+            other => panic!("Unexpected {other:?}"),
+        }
+        match i18n.try_t(&locales, "login.missing".try_into().unwrap(), None) {
+            Err(TranslateError::MissingAttribute {
+                id,
+                attribute,
+                locales,
+            }) => {
+                assert_eq!(id, "login");
+                assert_eq!(attribute, "missing");
+                assert_eq!(locales.to_vec(), expected);
+            }
+            other => panic!("Unexpected {other:?}"),
+        }
+        match i18n.try_t(&locales, "hello".try_into().unwrap(), None) {
+            Err(TranslateError::Format {
+                locale,
+                errors,
+                value,
+                ..
+            }) => {
+                assert_eq!(locale, expected[1]);
+                assert_eq!(errors.len(), 1);
+                assert_eq!(value, "Hello \u{2068}{$name}\u{2069}");
+            }
+            other => panic!("Unexpected {other:?}"),
+        }
+        assert_eq!(
+            i18n.t(&locales, "hello".try_into().unwrap(), None),
+            "Hello \u{2068}{$name}\u{2069}"
+        );
+        assert_eq!(
+            i18n.try_t(&locales, "login.placeholder".try_into().unwrap(), None)
+                .unwrap(),
+            "email@example.com"
+        );
+    }
+
+    #[test]
+    fn missing_key_policies() {
+        let build = || {
+            FluentMachine::build()
+                .add_resource("en", r#"hello = Hello"#)
+                .expect("Should add en")
+        };
+        let en: LanguageIdentifier = "en".parse().unwrap();
+        let key = "login.not-found".try_into().unwrap();
+
+        let i18n = build().finish().unwrap();
+        assert_eq!(i18n.t(&[&en], key, None), "login.not-found");
+        let i18n = build()
+            .set_missing_key_policy(MissingKeyPolicy::Empty)
+            .finish()
+            .unwrap();
+        assert_eq!(i18n.t(&[&en], key, None), "");
+        let i18n = build()
+            .set_missing_key_policy(MissingKeyPolicy::Marker)
+            .finish()
+            .unwrap();
+        assert_eq!(i18n.t(&[&en], key, None), "⟦login.not-found⟧");
+        let i18n = build()
+            .set_missing_key_policy(MissingKeyPolicy::Callback(Arc::new(
+                |key, args, locales| {
+                    format!(
+                        "{key} {} {}",
+                        args.map_or(0, |a| a.iter().count()),
+                        locales[0]
+                    )
+                },
+            )))
+            .finish()
+            .unwrap();
+        assert_eq!(
+            i18n.t(&[&en], key, Some(&f_args!["a" => 1])),
+            "login.not-found 1 en"
+        );
+        assert_eq!(i18n.t(&[&en], "hello".try_into().unwrap(), None), "Hello");
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "missing key login.not-found in locales [en]")]
+    fn missing_key_policy_panics_in_debug() {
+        let i18n = FluentMachine::build()
+            .add_resource("en", r#"hello = Hello"#)
+            .expect("Should add en")
+            .set_missing_key_policy(MissingKeyPolicy::PanicInDebug)
+            .finish()
+            .unwrap();
+        let en: LanguageIdentifier = "en".parse().unwrap();
+        i18n.t(&[&en], "login.not-found".try_into().unwrap(), None);
+    }
+
+    #[test]
+    fn write_t_and_t_cow_match_t() {
+        let i18n = FluentMachine::build()
+            .add_resource(
+                "en",
+                r#"
+title = Welcome
+login = Login
+    .placeholder = email@example.com
+hello = Hello {$name}.
+"#,
+            )
+            .expect("Should add en")
+            .set_missing_key_policy(MissingKeyPolicy::Marker)
+            .finish()
+            .expect("Should finish");
+        let en = i18n.negotiate_languages("en");
+        let args = f_args!["name" => "Joe"];
+
+        for key in ["title", "login.placeholder", "hello", "missing"] {
+            let key = key.try_into().unwrap();
+            let expected = i18n.t(&en, key, Some(&args));
+            let mut out = String::new();
+            i18n.write_t(&mut out, &en, key, Some(&args)).unwrap();
+            assert_eq!(out, expected);
+            assert_eq!(i18n.t_cow(&en, key, Some(&args)), expected);
+        }
+        assert!(matches!(
+            i18n.t_cow(&en, "login.placeholder".try_into().unwrap(), None),
+            Cow::Borrowed("email@example.com")
+        ));
+        assert!(matches!(
+            i18n.t_cow(&en, "missing".try_into().unwrap(), None),
+            Cow::Owned(_)
+        ));
+    }
+
+    #[test]
+    fn negotiate_languages_cached() {
+        let build = || {
+            FluentMachine::build()
+                .add_resource("en", r#"region = International"#)
+                .expect("Should add en")
+                .add_resource("en-US", r#"region = United States"#)
+                .expect("Should add en-US")
+                .add_resource("pt", r#"region = Internacional"#)
+                .expect("Should add pt")
+        };
+        let uncached = build().finish().unwrap();
+        assert_eq!(uncached.get_negotiation_cache_stats(), None);
+        let i18n = build().set_negotiation_cache(8).finish().unwrap();
+
+        for requested in ["pt;0.9, en-US;0.5", "en-US", "pt;0.9, en-US;0.5", "de"] {
+            assert_eq!(
+                i18n.negotiate_languages(requested),
+                uncached.negotiate_languages(requested)
+            );
+        }
+        let stats = i18n.get_negotiation_cache_stats().unwrap();
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.misses, 3);
+    }
+
+    #[test]
+    fn format_error_handler_receives_errors() {
+        let collected = Arc::new(Mutex::new(vec![]));
+        let collecting = Arc::clone(&collected);
+        let i18n = FluentMachine::build()
+            .add_resource("en", r#"hello = Hello {$name} {$surname}"#)
+            .expect("Should add en")
+            .add_resource("en-US", r#"region = United States"#)
+            .expect("Should add en-US")
+            .set_format_error_handler(Arc::new(move |key, locale, errors| {
+                collecting
+                    .lock()
+                    .unwrap()
+                    .push(format!("{key} {locale} {}", errors.len()));
+            }))
+            .finish()
+            .unwrap();
+        let locales = i18n.negotiate_languages("en-US");
+        let key = "hello".try_into().unwrap();
+        let args = f_args!["name" => "Joe"];
+
+        let translation = i18n.t_with_errors(&locales, key, Some(&args));
+        assert_eq!(
+            translation.value,
+            "Hello \u{2068}Joe\u{2069} \u{2068}{$surname}\u{2069}"
+        );
+        assert_eq!(translation.errors.len(), 1);
+        assert_eq!(translation.locale, Some("en".parse().unwrap()));
+        i18n.t(&locales, key, Some(&args));
+        i18n.write_t(&mut String::new(), &locales, key, Some(&args))
+            .unwrap();
+        i18n.t(&locales, "region".try_into().unwrap(), None);
+        assert_eq!(*collected.lock().unwrap(), vec!["hello en 1"; 3]);
+
+        let missing = i18n.t_with_errors(&locales, "missing".try_into().unwrap(), None);
+        assert_eq!(missing.value, "missing");
+        assert!(missing.errors.is_empty());
+        assert_eq!(missing.locale, None);
+    }
 }
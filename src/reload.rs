@@ -0,0 +1,367 @@
+/*!
+Hot reloading of [`FluentMachine`] when the locale files change
+*/
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, RwLock,
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, SystemTime},
+};
+
+use crate::{
+    builders::{FluentMachineBuilder, InheritanceSyntaxErrorHandling},
+    Error, FluentMachine,
+};
+
+/// Rebuilds a [`FluentMachine`], called on every reload
+pub type ReloadFn = Box<dyn Fn() -> Result<FluentMachine, Error> + Send + Sync>;
+
+/// Wraps a [`FluentMachine`] that can be rebuilt while in use.
+///
+/// A reload swaps the machine for new [`snapshot`](ReloadableFluentMachine::snapshot)s,
+/// readers holding the previous snapshot keep using it. If rebuilding fails, the previous
+/// machine is kept.
+///
+/// # Example
+/// ```rust
+/// use fi18n::reload::ReloadableFluentMachine;
+/// use std::{sync::Arc, time::Duration};
+///
+/// let i18n = Arc::new(
+///     ReloadableFluentMachine::from_locales("examples/locales/", |builder| {
+///         builder.set_fallback_locale("en-US")
+///     })
+///     .expect("Should load locales"),
+/// );
+/// // reloads on changes of `examples/locales/` until the watcher is dropped
+/// let _watcher = i18n.watch(Duration::from_secs(2)).expect("Should watch the locales");
+///
+/// let machine = i18n.snapshot();
+/// let uk = machine.localize_t("en-UK");
+/// assert_eq!(
+///     uk.t("football".try_into().unwrap(), None),
+///     "American Football is the biggest North American sport, with Super Bowl 112.3 million viewers."
+/// );
+/// ```
+pub struct ReloadableFluentMachine {
+    current: RwLock<Arc<FluentMachine>>,
+    build: ReloadFn,
+    // directory given to `from_locales`
+    path: Option<PathBuf>,
+}
+
+impl ReloadableFluentMachine {
+    /// Creates by calling `build`, which is called again on every reload.
+    ///
+    /// # Errors
+    ///
+    /// Returns the `build` error.
+    pub fn new<F>(build: F) -> Result<Self, Error>
+    where
+        F: Fn() -> Result<FluentMachine, Error> + Send + Sync + 'static,
+    {
+        let machine = build()?;
+        Ok(Self {
+            current: RwLock::new(Arc::new(machine)),
+            build: Box::new(build),
+            path: None,
+        })
+    }
+
+    /// Creates from a locales directory with the same inheritance rules of
+    /// [`FluentMachineInheritanceBuilder::load_locales`](crate::builders::FluentMachineInheritanceBuilder::load_locales),
+    /// `configure` sets the [`FluentMachineBuilder`] options.
+    ///
+    /// [`ReloadableFluentMachine::watch`] watches `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::FluentResourceError`] at the first file with syntax errors, or
+    /// any error returned by `configure` or [`FluentMachineBuilder::finish`].
+    pub fn from_locales<F>(path: &str, configure: F) -> Result<Self, Error>
+    where
+        F: Fn(FluentMachineBuilder) -> Result<FluentMachineBuilder, Error> + Send + Sync + 'static,
+    {
+        let root = path.to_string();
+        let mut reloadable = Self::new(move || {
+            let builder =
+                FluentMachine::build_with_inheritance(InheritanceSyntaxErrorHandling::AtAddSource)
+                    .load_locales(&root)?
+                    .build_inheritance()
+                    // syntax errors are returned by `load_locales`
+                    .unwrap_or_else(|(builder, _)| builder);
+            configure(builder)?.finish()
+        })?;
+        reloadable.path = Some(PathBuf::from(path));
+        Ok(reloadable)
+    }
+
+    /// Returns the current machine, cheap to call for every request.
+    pub fn snapshot(&self) -> Arc<FluentMachine> {
+        Arc::clone(&self.current.read().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    /// Rebuilds and swaps the machine.
+    ///
+    /// # Errors
+    ///
+    /// Returns the build error, keeping the previous machine.
+    pub fn reload(&self) -> Result<(), Error> {
+        let machine = Arc::new((self.build)()?);
+        *self.current.write().unwrap_or_else(|e| e.into_inner()) = machine;
+        Ok(())
+    }
+
+    /// Polls the directory given to [`ReloadableFluentMachine::from_locales`] every `interval`
+    /// and reloads when any file is added, removed or modified, retrying a failed reload until
+    /// it succeeds.
+    ///
+    /// Returns `None` when created with [`ReloadableFluentMachine::new`], check
+    /// [`ReloadableFluentMachine::watch_path`].
+    ///
+    /// Watching stops when [`ReloadWatcher`] is dropped.
+    pub fn watch(self: &Arc<Self>, interval: Duration) -> Option<ReloadWatcher> {
+        let path = self.path.as_ref()?;
+        Some(self.watch_path(path, interval))
+    }
+
+    /// Polls `path` every `interval` and reloads when any file is added, removed or modified.
+    ///
+    /// For machines created with [`ReloadableFluentMachine::new`], `path` should be the
+    /// directory read by `build`.
+    ///
+    /// Watching stops when [`ReloadWatcher`] is dropped.
+    pub fn watch_path<P: AsRef<Path>>(
+        self: &Arc<Self>,
+        path: P,
+        interval: Duration,
+    ) -> ReloadWatcher {
+        let stop = Arc::new(AtomicBool::new(false));
+        let machine = Arc::clone(self);
+        let path = path.as_ref().to_path_buf();
+        let stopping = Arc::clone(&stop);
+        // files of the last successful load, and of the last failed reload
+        let mut last = fingerprint(&path).ok();
+        let mut failed = None;
+        let handle = thread::spawn(move || {
+            while !stopping.load(Ordering::Relaxed) {
+                thread::park_timeout(interval);
+                if stopping.load(Ordering::Relaxed) {
+                    break;
+                }
+                let current = match fingerprint(&path) {
+                    Ok(current) => Some(current),
+                    Err(e) => {
+                        log::warn!(target: "ReloadableFluentMachine", "Failed to read `{}`: {e}", path.display());
+                        continue;
+                    }
+                };
+                // retried every interval until a reload succeeds, warning once per change
+                if current == last {
+                    continue;
+                }
+                match machine.reload() {
+                    Ok(()) => {
+                        log::info!(target: "ReloadableFluentMachine", "Reloaded fluent translations");
+                        last = current;
+                        failed = None;
+                    }
+                    Err(e) => {
+                        if failed != current {
+                            log::warn!(
+                                target: "ReloadableFluentMachine",
+                                "Failed to reload fluent translations, keeping previous: {e}"
+                            );
+                        }
+                        failed = current;
+                    }
+                }
+            }
+        });
+        ReloadWatcher {
+            stop,
+            handle: Some(handle),
+        }
+    }
+}
+
+/// Stops [`ReloadableFluentMachine::watch`] when dropped
+pub struct ReloadWatcher {
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Drop for ReloadWatcher {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            handle.thread().unpark();
+            let _ = handle.join();
+        }
+    }
+}
+
+// sorted path, modification time and size of all files under `p`
+fn fingerprint(p: &Path) -> Result<Vec<(PathBuf, SystemTime, u64)>, io::Error> {
+    fn walk(p: &Path, out: &mut Vec<(PathBuf, SystemTime, u64)>) -> Result<(), io::Error> {
+        for entry in fs::read_dir(p)? {
+            let path = entry?.path();
+            let meta = fs::metadata(&path)?;
+            if meta.is_dir() {
+                walk(&path, out)?;
+            } else {
+                out.push((path, meta.modified()?, meta.len()));
+            }
+        }
+        Ok(())
+    }
+    let mut out = vec![];
+    walk(p, &mut out)?;
+    out.sort();
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LanguageIdentifier;
+    use std::sync::atomic::AtomicUsize;
+
+    fn temp_locales(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("fi18n-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("en")).unwrap();
+        fs::create_dir_all(dir.join("en-US")).unwrap();
+        fs::write(
+            dir.join("en/main.ftl"),
+            "region = International\nhello = Hello",
+        )
+        .unwrap();
+        fs::write(dir.join("en-US/main.ftl"), "region = United States").unwrap();
+        dir
+    }
+
+    #[test]
+    fn reload_keeps_previous_on_errors() {
+        let dir = temp_locales("reload");
+        let i18n =
+            ReloadableFluentMachine::from_locales(dir.to_str().unwrap(), Ok).expect("Should load");
+        let us: LanguageIdentifier = "en-US".parse().unwrap();
+        let before = i18n.snapshot();
+        assert_eq!(before.t(&[&us], "hello".try_into().unwrap(), None), "Hello");
+
+        fs::write(dir.join("en-US/main.ftl"), "region = USA\nhello = Hi").unwrap();
+        i18n.reload().expect("Should reload");
+        let after = i18n.snapshot();
+        assert_eq!(after.t(&[&us], "hello".try_into().unwrap(), None), "Hi");
+        assert_eq!(
+            before.t(&[&us], "region".try_into().unwrap(), None),
+            "United States",
+            "previous snapshot is unchanged"
+        );
+
+        fs::write(dir.join("en-US/main.ftl"), "region =").unwrap();
+        assert!(i18n.reload().is_err());
+        assert_eq!(
+            i18n.snapshot()
+                .t(&[&us], "region".try_into().unwrap(), None),
+            "USA"
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn watch_reloads_on_changes() {
+        let dir = temp_locales("watch");
+        let i18n = Arc::new(
+            ReloadableFluentMachine::from_locales(dir.to_str().unwrap(), Ok).expect("Should load"),
+        );
+        let en: LanguageIdentifier = "en".parse().unwrap();
+        let watcher = i18n
+            .watch(Duration::from_millis(10))
+            .expect("Should watch the locales directory");
+
+        fs::write(dir.join("en/main.ftl"), "region = Worldwide").unwrap();
+        let mut reloaded = false;
+        for _ in 0..200 {
+            if i18n
+                .snapshot()
+                .t(&[&en], "region".try_into().unwrap(), None)
+                == "Worldwide"
+            {
+                reloaded = true;
+                break;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        drop(watcher);
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(reloaded, "Should have reloaded");
+    }
+
+    #[test]
+    fn watch_retries_failed_reloads() {
+        let dir = temp_locales("retry");
+        let failing = Arc::new(AtomicBool::new(false));
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let (fail, attempted) = (Arc::clone(&failing), Arc::clone(&attempts));
+        let i18n = Arc::new(
+            ReloadableFluentMachine::from_locales(dir.to_str().unwrap(), move |builder| {
+                attempted.fetch_add(1, Ordering::SeqCst);
+                match fail.load(Ordering::SeqCst) {
+                    true => Err(Error::Unexpected),
+                    false => Ok(builder),
+                }
+            })
+            .expect("Should load"),
+        );
+        let en: LanguageIdentifier = "en".parse().unwrap();
+        let watcher = i18n
+            .watch(Duration::from_millis(10))
+            .expect("Should watch the locales directory");
+
+        failing.store(true, Ordering::SeqCst);
+        fs::write(dir.join("en/main.ftl"), "region = Worldwide").unwrap();
+        for _ in 0..200 {
+            if attempts.load(Ordering::SeqCst) > 2 {
+                break;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert!(attempts.load(Ordering::SeqCst) > 2, "Should retry");
+        failing.store(false, Ordering::SeqCst);
+        let mut reloaded = false;
+        for _ in 0..200 {
+            if i18n
+                .snapshot()
+                .t(&[&en], "region".try_into().unwrap(), None)
+                == "Worldwide"
+            {
+                reloaded = true;
+                break;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        drop(watcher);
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(reloaded, "Should reload without another change");
+    }
+
+    #[test]
+    fn watch_requires_locales_directory() {
+        let i18n = Arc::new(
+            ReloadableFluentMachine::new(|| {
+                FluentMachine::build()
+                    .add_resource("en", "hello = Hello")?
+                    .finish()
+            })
+            .expect("Should build"),
+        );
+        assert!(i18n.watch(Duration::from_millis(10)).is_none());
+    }
+}
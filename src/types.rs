@@ -0,0 +1,273 @@
+/*!
+Domain values formatted in the bundle locale.
+
+Implement [`FluentLocalized`] for a type and pass it wrapped in [`Localized`] as an
+argument, it is formatted with the locale of the bundle that resolves the message.
+
+With the `fn-number` feature, [`FormatContext::format_number`] formats numbers with the
+bundle locale symbols.
+
+# Example
+
+```
+use std::borrow::Cow;
+use fi18n::{
+    f_args,
+    types::{FluentLocalized, FormatContext, Localized},
+    FluentMachine, LanguageIdentifier,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+struct Distance(f64);
+
+impl FluentLocalized for Distance {
+    fn format(&self, cx: &FormatContext<'_>) -> Cow<'static, str> {
+        match cx.locale().region.map(|r| r.to_string()).as_deref() {
+            Some("US") => format!("{:.1} mi", self.0 / 1.609_344),
+            _ => format!("{:.1} km", self.0),
+        }
+        .into()
+    }
+}
+
+let i18n = FluentMachine::build()
+    .add_resource("en-US", "away = { $distance } away")
+    .expect("Should add en-US")
+    .add_resource("pt-PT", "away = a { $distance }")
+    .expect("Should add pt-PT")
+    .set_fallback_locale("pt-PT")
+    .expect("Should set pt-PT")
+    .finish()
+    .expect("Should finish");
+
+let args = f_args!["distance" => Localized(Distance(12.5))];
+let us: LanguageIdentifier = "en-US".parse().unwrap();
+let pt: LanguageIdentifier = "pt-PT".parse().unwrap();
+assert_eq!(
+    i18n.t(&[&us], "away".try_into().unwrap(), Some(&args)),
+    "\u{2068}7.8 mi\u{2069} away"
+);
+assert_eq!(
+    i18n.t(&[&pt], "away".try_into().unwrap(), Some(&args)),
+    "a \u{2068}12.5 km\u{2069}"
+);
+```
+*/
+
+use std::{borrow::Cow, convert::Infallible, fmt};
+
+#[cfg(feature = "fn-number")]
+use fluent_bundle::types::FluentNumberOptions;
+use fluent_bundle::types::FluentType;
+use intl_memoizer::{concurrent, IntlLangMemoizer, Memoizable};
+
+#[cfg(feature = "fn-number")]
+use crate::functions::NumberFormat;
+use crate::{FluentValue, LanguageIdentifier};
+
+/// A value formatted with the bundle locale, passed as an argument with [`Localized`].
+pub trait FluentLocalized: fmt::Debug + PartialEq + Clone + Send + 'static {
+    /// Formats the value for [`FormatContext::locale`].
+    fn format(&self, cx: &FormatContext<'_>) -> Cow<'static, str>;
+}
+
+/// Wraps a [`FluentLocalized`] value into a [`FluentValue`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Localized<T>(pub T);
+
+impl<'a, T: FluentLocalized> From<Localized<T>> for FluentValue<'a> {
+    fn from(value: Localized<T>) -> Self {
+        FluentValue::Custom(Box::new(value))
+    }
+}
+
+impl<T: FluentLocalized> FluentType for Localized<T> {
+    fn duplicate(&self) -> Box<dyn FluentType + Send> {
+        Box::new(self.clone())
+    }
+
+    fn as_string(&self, intls: &IntlLangMemoizer) -> Cow<'static, str> {
+        self.0.format(&FormatContext::new(Intls::Local(intls)))
+    }
+
+    fn as_string_threadsafe(&self, intls: &concurrent::IntlLangMemoizer) -> Cow<'static, str> {
+        self.0.format(&FormatContext::new(Intls::Concurrent(intls)))
+    }
+}
+
+enum Intls<'a> {
+    Local(&'a IntlLangMemoizer),
+    Concurrent(&'a concurrent::IntlLangMemoizer),
+}
+
+// the memoizer locale, memoized as a formatter since the memoizer does not expose it
+struct Locale(LanguageIdentifier);
+
+impl Memoizable for Locale {
+    type Args = ();
+    type Error = Infallible;
+
+    fn construct(lang: LanguageIdentifier, _: Self::Args) -> Result<Self, Self::Error> {
+        Ok(Self(lang))
+    }
+}
+
+/// Bundle locale and memoized intl formatters, given to [`FluentLocalized::format`].
+pub struct FormatContext<'a> {
+    intls: Intls<'a>,
+    locale: LanguageIdentifier,
+}
+
+impl fmt::Debug for FormatContext<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FormatContext")
+            .field("locale", &self.locale)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a> FormatContext<'a> {
+    fn new(intls: Intls<'a>) -> Self {
+        let locale = match intls {
+            Intls::Local(m) => m.with_try_get::<Locale, _, _>((), |l| l.0.clone()),
+            Intls::Concurrent(m) => m.with_try_get::<Locale, _, _>((), |l| l.0.clone()),
+        }
+        .unwrap_or_else(|e| match e {});
+        Self { intls, locale }
+    }
+
+    /// Locale of the bundle formatting the message.
+    pub fn locale(&self) -> &LanguageIdentifier {
+        &self.locale
+    }
+
+    /// Runs `cb` with the intl formatter `I` for the bundle locale, constructed once per
+    /// bundle and `args`.
+    pub fn memoize<I, R, U>(&self, args: I::Args, cb: U) -> Result<R, I::Error>
+    where
+        I: Memoizable + Send + Sync + 'static,
+        I::Args: Send + Sync + 'static,
+        U: FnOnce(&I) -> R,
+    {
+        match self.intls {
+            Intls::Local(m) => m.with_try_get::<I, R, U>(args, cb),
+            Intls::Concurrent(m) => m.with_try_get::<I, R, U>(args, cb),
+        }
+    }
+
+    /// Formats `value` with the bundle locale number symbols.
+    #[cfg(feature = "fn-number")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "fn-number")))]
+    pub fn format_number(&self, value: f64) -> String {
+        self.format_number_with(value, FluentNumberOptions::default())
+    }
+
+    /// Formats `value` with the bundle locale number symbols and `options`.
+    #[cfg(feature = "fn-number")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "fn-number")))]
+    pub fn format_number_with(&self, value: f64, options: FluentNumberOptions) -> String {
+        self.memoize::<NumberFormat, _, _>(options, |nf| nf.format(value))
+            .unwrap_or_else(|e| match e {})
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{f_args, FluentMachine};
+    #[cfg(feature = "fn-number")]
+    use fluent_bundle::types::FluentNumberStyle;
+
+    #[cfg(feature = "fn-number")]
+    #[derive(Debug, Clone, PartialEq)]
+    struct Money {
+        cents: i64,
+        currency: &'static str,
+    }
+
+    #[cfg(feature = "fn-number")]
+    impl FluentLocalized for Money {
+        fn format(&self, cx: &FormatContext<'_>) -> Cow<'static, str> {
+            let options = FluentNumberOptions {
+                style: FluentNumberStyle::Currency,
+                currency: Some(self.currency.to_string()),
+                ..Default::default()
+            };
+            cx.format_number_with(self.cents as f64 / 100.0, options)
+                .into()
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct UserName(String);
+
+    impl FluentLocalized for UserName {
+        fn format(&self, cx: &FormatContext<'_>) -> Cow<'static, str> {
+            match cx.locale().language.as_str() {
+                "pt" => format!("{} (utilizador)", self.0),
+                _ => format!("{} (user)", self.0),
+            }
+            .into()
+        }
+    }
+
+    #[test]
+    fn formats_with_bundle_locale() {
+        let i18n = FluentMachine::build()
+            .add_resource("en", "hello = Hello { $user }")
+            .expect("Should add en")
+            .add_resource("pt-PT", "hello = Olá { $user }")
+            .expect("Should add pt-PT")
+            .finish()
+            .expect("Should finish");
+        let args = f_args!["user" => Localized(UserName("ana".into()))];
+        let en: LanguageIdentifier = "en".parse().unwrap();
+        let pt: LanguageIdentifier = "pt-PT".parse().unwrap();
+        assert_eq!(
+            i18n.t(&[&en], "hello".try_into().unwrap(), Some(&args)),
+            "Hello \u{2068}ana (user)\u{2069}"
+        );
+        assert_eq!(
+            i18n.t(&[&pt], "hello".try_into().unwrap(), Some(&args)),
+            "Olá \u{2068}ana (utilizador)\u{2069}"
+        );
+    }
+
+    #[cfg(feature = "fn-number")]
+    #[test]
+    fn formats_numbers_with_bundle_locale() {
+        let i18n = FluentMachine::build()
+            .add_resource("en", "paid = { $user } paid { $price }")
+            .expect("Should add en")
+            .add_resource("pt-PT", "paid = { $user } pagou { $price }")
+            .expect("Should add pt-PT")
+            .finish()
+            .expect("Should finish");
+        let args = f_args![
+            "user" => Localized(UserName("ana".into())),
+            "price" => Localized(Money { cents: 123_456, currency: "EUR" }),
+        ];
+        let en: LanguageIdentifier = "en".parse().unwrap();
+        let pt: LanguageIdentifier = "pt-PT".parse().unwrap();
+        assert_eq!(
+            i18n.t(&[&en], "paid".try_into().unwrap(), Some(&args)),
+            "\u{2068}ana (user)\u{2069} paid \u{2068}€1,234.56\u{2069}"
+        );
+        assert_eq!(
+            i18n.t(&[&pt], "paid".try_into().unwrap(), Some(&args)),
+            "\u{2068}ana (utilizador)\u{2069} pagou \u{2068}1\u{a0}234,56\u{a0}€\u{2069}"
+        );
+    }
+
+    #[test]
+    fn context_locale_and_memoizer() {
+        let intls = concurrent::IntlLangMemoizer::new("pt-BR".parse().unwrap());
+        let cx = FormatContext::new(Intls::Concurrent(&intls));
+        assert_eq!(cx.locale(), &"pt-BR".parse::<LanguageIdentifier>().unwrap());
+        #[cfg(feature = "fn-number")]
+        assert_eq!(cx.format_number(5000.5), "5.000,5");
+        let intls = IntlLangMemoizer::new("en".parse().unwrap());
+        let value = Localized(UserName("joe".into()));
+        assert_eq!(value.as_string(&intls), "joe (user)");
+    }
+}
@@ -0,0 +1,419 @@
+/*!
+Strict validation of the messages of every available locale, check
+[`FluentMachineBuilder::set_strict`](crate::builders::FluentMachineBuilder::set_strict)
+*/
+
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    fmt,
+};
+
+use fluent_syntax::{
+    ast::{CallArguments, Entry, Expression, InlineExpression, Pattern, PatternElement},
+    parser::{self, ErrorKind},
+};
+
+use crate::{FluentMachine, LanguageIdentifier};
+
+/// Problem found by [`FluentMachine::validate`]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ValidationIssue {
+    /// Message, or term, references a message, or term, that doesn't exist in the locale
+    UndefinedReference {
+        locale: LanguageIdentifier,
+        /// Message id, terms start with `-`
+        id: String,
+        /// Reference as `message`, `message.attribute`, `-term` or `-term.attribute`
+        reference: String,
+    },
+    /// Locale doesn't have a fallback message, or attribute
+    MissingKey {
+        locale: LanguageIdentifier,
+        /// Key as `message` or `message.attribute`
+        key: String,
+    },
+    /// Message uses different `$variables` than the fallback message
+    VariablesMismatch {
+        locale: LanguageIdentifier,
+        id: String,
+        /// Fallback variables, sorted
+        expected: Vec<String>,
+        /// Locale variables, sorted
+        found: Vec<String>,
+    },
+    /// Select expression without a `*[default]` variant, kept unparsed by the lenient builders
+    SelectWithoutDefault {
+        locale: LanguageIdentifier,
+        /// Source of the entry
+        content: String,
+    },
+    /// Other unparsed entry, kept by the lenient builders
+    Junk {
+        locale: LanguageIdentifier,
+        /// Source of the entry
+        content: String,
+    },
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UndefinedReference {
+                locale,
+                id,
+                reference,
+            } => write!(f, "{locale}: `{id}` references undefined `{reference}`"),
+            Self::MissingKey { locale, key } => write!(f, "{locale}: missing `{key}`"),
+            Self::VariablesMismatch {
+                locale,
+                id,
+                expected,
+                found,
+            } => write!(
+                f,
+                "{locale}: `{id}` uses variables {found:?}, fallback uses {expected:?}"
+            ),
+            Self::SelectWithoutDefault { locale, content } => {
+                write!(
+                    f,
+                    "{locale}: select without default in `{}`",
+                    content.trim()
+                )
+            }
+            Self::Junk { locale, content } => {
+                write!(f, "{locale}: unparsed `{}`", content.trim())
+            }
+        }
+    }
+}
+
+impl FluentMachine {
+    /// Validates the messages of every available locale, returns the problems found, sorted
+    /// by locale.
+    ///
+    /// Checks, in each locale, references to undefined messages and terms and unparsed entries,
+    /// like select expressions without default, and, against
+    /// [`FluentMachine::get_fallback_locale`], missing keys and messages using different
+    /// `$variables`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use fi18n::{validation::ValidationIssue, FluentMachine};
+    ///
+    /// let i18n = FluentMachine::build()
+    ///     .add_resource("en", "hello = Hello { $name }\nbye = Bye").expect("Should add en")
+    ///     .add_resource("pt", "hello = Olá { $user } { -brand }").expect("Should add pt")
+    ///     .finish().expect("Should finish");
+    ///
+    /// let issues = i18n.validate();
+    /// assert_eq!(issues.len(), 3);
+    /// assert!(matches!(&issues[0], ValidationIssue::UndefinedReference { reference, .. } if reference == "-brand"));
+    /// assert!(matches!(&issues[1], ValidationIssue::MissingKey { key, .. } if key == "bye"));
+    /// assert!(matches!(&issues[2], ValidationIssue::VariablesMismatch { id, .. } if id == "hello"));
+    /// ```
+    pub fn validate(&self) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+        let reference = self.variables(&self.fallback);
+        let coverage = self.coverage();
+        for locale in self.available.iter() {
+            self.validate_entries(locale, &mut issues);
+            if *locale == self.fallback {
+                continue;
+            }
+            if let Some(c) = coverage.locales.iter().find(|c| &c.locale == locale) {
+                let mut missing: Vec<&String> = c
+                    .missing_messages
+                    .iter()
+                    .chain(c.missing_attributes.iter())
+                    .collect();
+                missing.sort();
+                issues.extend(missing.into_iter().map(|key| ValidationIssue::MissingKey {
+                    locale: locale.clone(),
+                    key: key.clone(),
+                }));
+            }
+            let mut variables: Vec<_> = self.variables(locale).into_iter().collect();
+            variables.sort();
+            for (id, found) in variables {
+                match reference.get(id) {
+                    Some(expected) if *expected != found => {
+                        issues.push(ValidationIssue::VariablesMismatch {
+                            locale: locale.clone(),
+                            id: id.to_string(),
+                            expected: expected.iter().map(|v| v.to_string()).collect(),
+                            found: found.iter().map(|v| v.to_string()).collect(),
+                        })
+                    }
+                    _ => {}
+                }
+            }
+        }
+        issues
+    }
+
+    // undefined references and unparsed entries of `locale`
+    fn validate_entries(&self, locale: &LanguageIdentifier, issues: &mut Vec<ValidationIssue>) {
+        let entries = match self.resources.get(locale) {
+            Some(resources) => resources.iter().flat_map(|r| r.entries()),
+            None => return,
+        };
+        // defined keys, `message`, `message.attribute`, `-term` and `-term.attribute`
+        let mut defined = HashSet::new();
+        let mut patterns = Vec::new();
+        for entry in entries {
+            let (id, value, attributes) = match entry {
+                Entry::Message(msg) => {
+                    (msg.id.name.to_string(), msg.value.as_ref(), &msg.attributes)
+                }
+                Entry::Term(term) => (
+                    format!("-{}", term.id.name),
+                    Some(&term.value),
+                    &term.attributes,
+                ),
+                Entry::Junk { content } => {
+                    issues.push(junk(locale, content));
+                    continue;
+                }
+                _ => continue,
+            };
+            for attr in attributes {
+                defined.insert(format!("{id}.{}", attr.id.name));
+                patterns.push((id.clone(), &attr.value));
+            }
+            if let Some(value) = value {
+                patterns.push((id.clone(), value));
+            }
+            defined.insert(id);
+        }
+        let mut reported = HashSet::new();
+        for (id, pattern) in patterns {
+            let mut refs = References::default();
+            refs.pattern(pattern);
+            for reference in refs.entries {
+                if !defined.contains(&reference) && reported.insert((id.clone(), reference.clone()))
+                {
+                    issues.push(ValidationIssue::UndefinedReference {
+                        locale: locale.clone(),
+                        id: id.clone(),
+                        reference,
+                    });
+                }
+            }
+        }
+    }
+
+    // variables of each message of `locale`, value and attributes
+    fn variables(&self, locale: &LanguageIdentifier) -> HashMap<&str, BTreeSet<&str>> {
+        self.message_ids(locale)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|id| {
+                let msg = self.message_entry(locale, id)?;
+                let mut refs = References::default();
+                msg.value
+                    .iter()
+                    .chain(msg.attributes.iter().map(|a| &a.value))
+                    .for_each(|p| refs.pattern(p));
+                Some((id, refs.variables))
+            })
+            .collect()
+    }
+}
+
+fn junk(locale: &LanguageIdentifier, content: &str) -> ValidationIssue {
+    let missing_default = parser::parse(content).is_err_and(|(_, errs)| {
+        errs.iter()
+            .any(|e| e.kind == ErrorKind::MissingDefaultVariant)
+    });
+    if missing_default {
+        ValidationIssue::SelectWithoutDefault {
+            locale: locale.clone(),
+            content: content.to_string(),
+        }
+    } else {
+        ValidationIssue::Junk {
+            locale: locale.clone(),
+            content: content.to_string(),
+        }
+    }
+}
+
+/// Variables and references of patterns
+#[derive(Default)]
+struct References<'s> {
+    variables: BTreeSet<&'s str>,
+    entries: Vec<String>,
+}
+
+impl<'s> References<'s> {
+    fn pattern(&mut self, pattern: &Pattern<&'s str>) {
+        for element in pattern.elements.iter() {
+            if let PatternElement::Placeable { expression } = element {
+                self.expression(expression);
+            }
+        }
+    }
+
+    fn expression(&mut self, expression: &Expression<&'s str>) {
+        match expression {
+            Expression::Select { selector, variants } => {
+                self.inline(selector);
+                for variant in variants {
+                    self.pattern(&variant.value);
+                }
+            }
+            Expression::Inline(inline) => self.inline(inline),
+        }
+    }
+
+    fn inline(&mut self, inline: &InlineExpression<&'s str>) {
+        match inline {
+            InlineExpression::VariableReference { id } => {
+                self.variables.insert(id.name);
+            }
+            InlineExpression::MessageReference { id, attribute } => {
+                self.entries.push(match attribute {
+                    Some(attr) => format!("{}.{}", id.name, attr.name),
+                    None => id.name.to_string(),
+                })
+            }
+            InlineExpression::TermReference {
+                id,
+                attribute,
+                arguments,
+            } => {
+                self.entries.push(match attribute {
+                    Some(attr) => format!("-{}.{}", id.name, attr.name),
+                    None => format!("-{}", id.name),
+                });
+                if let Some(arguments) = arguments {
+                    self.arguments(arguments);
+                }
+            }
+            InlineExpression::FunctionReference { arguments, .. } => self.arguments(arguments),
+            InlineExpression::Placeable { expression } => self.expression(expression),
+            InlineExpression::StringLiteral { .. } | InlineExpression::NumberLiteral { .. } => {}
+        }
+    }
+
+    fn arguments(&mut self, arguments: &CallArguments<&'s str>) {
+        for inline in arguments.positional.iter() {
+            self.inline(inline);
+        }
+        for named in arguments.named.iter() {
+            self.inline(&named.value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ValidationIssue;
+    use crate::{
+        builders::InheritanceSyntaxErrorHandling, Error, FluentMachine, LanguageIdentifier,
+    };
+
+    #[test]
+    fn reports_every_problem() {
+        let (builder, errors) =
+            FluentMachine::build_with_inheritance(InheritanceSyntaxErrorHandling::AtBuild)
+                .add_source(
+                    "en",
+                    r#"
+-brand = Example
+    .gender = masculine
+hello = Hello { $name } from { -brand }
+emails = { $count ->
+    [one] One email for { $name }
+   *[other] { $count } emails for { $name }
+}
+login = Login
+    .title = { login } at { -brand }
+"#,
+                    None,
+                )
+                .expect("Should add en")
+                .add_source(
+                    "pt",
+                    r#"
+hello = Olá { $user } de { -marca }
+emails = { $count ->
+    [one] Um email
+}
+login = Entrar
+    .title = { login.missing }
+"#,
+                    None,
+                )
+                .expect("Should add pt")
+                .build_inheritance()
+                .expect_err("pt has a select without default");
+        assert!(!errors.is_empty());
+
+        let i18n = builder.finish().expect("Should finish without strict");
+        let pt: LanguageIdentifier = "pt".parse().unwrap();
+        let issues = i18n.validate();
+        assert_eq!(
+            issues,
+            vec![
+                ValidationIssue::SelectWithoutDefault {
+                    locale: pt.clone(),
+                    content: "emails = { $count ->\n    [one] Um email\n}\n".into(),
+                },
+                ValidationIssue::UndefinedReference {
+                    locale: pt.clone(),
+                    id: "hello".into(),
+                    reference: "-marca".into(),
+                },
+                ValidationIssue::UndefinedReference {
+                    locale: pt.clone(),
+                    id: "login".into(),
+                    reference: "login.missing".into(),
+                },
+                ValidationIssue::MissingKey {
+                    locale: pt.clone(),
+                    key: "emails".into(),
+                },
+                ValidationIssue::VariablesMismatch {
+                    locale: pt,
+                    id: "hello".into(),
+                    expected: vec!["name".into()],
+                    found: vec!["user".into()],
+                },
+            ]
+        );
+        assert_eq!(
+            issues[4].to_string(),
+            r#"pt: `hello` uses variables ["user"], fallback uses ["name"]"#
+        );
+    }
+
+    #[test]
+    fn strict_finish() {
+        let builder = || {
+            FluentMachine::build()
+                .add_resource("en", "hello = Hello { $name }\nbye = Bye")
+                .expect("Should add en")
+                .add_resource("pt", "hello = Olá { $name }")
+                .expect("Should add pt")
+        };
+        assert!(builder().finish().is_ok());
+        match builder().set_strict(true).finish() {
+            Err(Error::Validation(issues)) => assert_eq!(
+                issues,
+                vec![ValidationIssue::MissingKey {
+                    locale: "pt".parse().unwrap(),
+                    key: "bye".into(),
+                }]
+            ),
+            _ => panic!("Should fail strict validation"),
+        }
+        assert!(builder()
+            .add_resource("pt", "bye = Adeus")
+            .expect("Should add bye")
+            .set_strict(true)
+            .finish()
+            .is_ok());
+    }
+}